@@ -1,10 +1,43 @@
 use std::env;
 use std::path::Path;
 
+/// `testing` 特性下由 SDK 内置 Mock 宿主接管的导入接口（WIT 接口名, Rust 模块名）
+const MOCK_IMPORTS: &[(&str, &str)] = &[
+    ("sql", "sql"),
+    ("stream-io", "stream_io"),
+    ("ffmpeg", "ffmpeg"),
+    ("context", "context"),
+    ("event-bus", "event_bus"),
+    ("http-client", "http_client"),
+];
+
 fn main() {
     let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
 
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo::rustc-check-cfg=cfg(vtx_mock_host)");
+
+    // 仅在原生目标上启用 Mock 宿主；Wasm 目标始终链接真实的宿主导入
+    let mock_host = env::var_os("CARGO_FEATURE_TESTING").is_some()
+        && env::var("CARGO_CFG_TARGET_ARCH").as_deref() != Ok("wasm32");
+
+    let with_option = if mock_host {
+        println!("cargo:rustc-cfg=vtx_mock_host");
+
+        let (package, version) = wit_package_id(vtx_protocol::WIT_CONTENT);
+        let entries: String = MOCK_IMPORTS
+            .iter()
+            .map(|(iface, module)| {
+                format!(
+                    "\"{}/{}@{}\": crate::testing::imports::{},\n",
+                    package, iface, version, module
+                )
+            })
+            .collect();
+        format!("with: {{\n{}}},", entries)
+    } else {
+        String::new()
+    };
 
     let bindings_code = format!(
         r#####"
@@ -15,9 +48,11 @@ fn main() {
             "####,
             pub_export_macro: true,
             default_bindings_module: "vtx_sdk::bindings",
+            {with_option}
         }});
         "#####,
-        content = vtx_protocol::WIT_CONTENT
+        content = vtx_protocol::WIT_CONTENT,
+        with_option = with_option,
     );
 
     let dest_path = Path::new(&out_dir).join("bindings.rs");
@@ -25,3 +60,18 @@ fn main() {
     // IO 操作：写入生成的 Rust 代码文件到 OUT_DIR
     std::fs::write(&dest_path, bindings_code).expect("Failed to write bindings.rs");
 }
+
+/// 从 WIT 的 `package ns:name@version;` 声明中解析包名与版本
+fn wit_package_id(wit: &str) -> (String, String) {
+    let decl = wit
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("package "))
+        .expect("WIT package declaration not found");
+    let (name, version) = decl
+        .trim_end_matches(';')
+        .trim()
+        .split_once('@')
+        .expect("WIT package declaration has no version");
+    (name.to_string(), version.to_string())
+}
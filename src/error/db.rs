@@ -0,0 +1,173 @@
+//! Structured classification of SQL failures.
+//!
+//! 宿主 SQL 接口的错误通常直接透传 SQLite 消息（如 `UNIQUE constraint failed: users.name`），
+//! 也可能使用 `error::host` 的结构化错误码（如 `sql.unique_violation`、`SQLITE_BUSY`）。
+//! 本模块将二者归类为 `DbErrorKind`，由 `db` 模块映射为 `VtxError::Db`：
+//!
+//! | 分类 | 错误码 | HTTP 状态码 | 典型消息 |
+//! |------|--------|-------------|----------|
+//! | `UniqueViolation` | `unique_violation` | 409 | `UNIQUE constraint failed: t.c` |
+//! | `ForeignKeyViolation` | `foreign_key_violation` | 409 | `FOREIGN KEY constraint failed` |
+//! | `NotNullViolation` | `not_null_violation` | 400 | `NOT NULL constraint failed: t.c` |
+//! | `CheckViolation` | `check_violation` | 400 | `CHECK constraint failed: c` |
+//! | `Busy` | `database_busy` | 503 | `database is locked` |
+//! | `Syntax` | `sql_error` | 500 | `near "SELEC": syntax error`、`no such table: t` |
+//! | `ReadOnly` | `read_only` | 403 | `attempt to write a readonly database` |
+
+use super::host::HostError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// SQL 错误分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DbErrorKind {
+    /// 唯一约束（含主键）冲突
+    UniqueViolation,
+    /// 外键约束失败
+    ForeignKeyViolation,
+    /// 非空约束失败
+    NotNullViolation,
+    /// CHECK 约束失败
+    CheckViolation,
+    /// 数据库被锁定或繁忙（可重试）
+    Busy,
+    /// SQL 语法或 schema 错误（表、列不存在等）
+    Syntax,
+    /// 数据库只读
+    ReadOnly,
+}
+
+impl DbErrorKind {
+    /// 稳定的机器可读错误码
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::UniqueViolation => "unique_violation",
+            Self::ForeignKeyViolation => "foreign_key_violation",
+            Self::NotNullViolation => "not_null_violation",
+            Self::CheckViolation => "check_violation",
+            Self::Busy => "database_busy",
+            Self::Syntax => "sql_error",
+            Self::ReadOnly => "read_only",
+        }
+    }
+
+    /// 对应的 HTTP 状态码
+    pub fn status(self) -> u16 {
+        match self {
+            Self::UniqueViolation | Self::ForeignKeyViolation => 409,
+            Self::NotNullViolation | Self::CheckViolation => 400,
+            Self::Busy => 503,
+            Self::Syntax => 500,
+            Self::ReadOnly => 403,
+        }
+    }
+
+    /// 稍后重试可能成功
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Busy)
+    }
+
+    /// 根据错误码分类（大小写不敏感，支持 `sql.` 命名空间与 `SQLITE_` 前缀）
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.to_ascii_lowercase().replace('-', "_");
+        let code = code.rsplit('.').next().unwrap_or_default();
+        let code = code.strip_prefix("sqlite_").unwrap_or(code);
+
+        match code {
+            "unique_violation" | "constraint_unique" | "constraint_primarykey" => {
+                Some(Self::UniqueViolation)
+            }
+            "foreign_key_violation" | "constraint_foreignkey" => Some(Self::ForeignKeyViolation),
+            "not_null_violation" | "constraint_notnull" => Some(Self::NotNullViolation),
+            "check_violation" | "constraint_check" => Some(Self::CheckViolation),
+            "busy" | "locked" | "database_busy" | "busy_snapshot" | "busy_recovery" => {
+                Some(Self::Busy)
+            }
+            "syntax" | "syntax_error" | "sql_error" => Some(Self::Syntax),
+            "readonly" | "read_only_database" => Some(Self::ReadOnly),
+            _ => None,
+        }
+    }
+
+    /// 按 SQLite 错误消息分类
+    pub fn from_message(message: &str) -> Option<Self> {
+        let lower = message.to_lowercase();
+        let has = |needle: &str| lower.contains(needle);
+
+        if has("unique constraint failed") || has("primary key must be unique") {
+            Some(Self::UniqueViolation)
+        } else if has("foreign key constraint failed") {
+            Some(Self::ForeignKeyViolation)
+        } else if has("not null constraint failed") {
+            Some(Self::NotNullViolation)
+        } else if has("check constraint failed") {
+            Some(Self::CheckViolation)
+        } else if has("database is locked")
+            || has("database table is locked")
+            || has("database is busy")
+        {
+            Some(Self::Busy)
+        } else if has("readonly database") {
+            Some(Self::ReadOnly)
+        } else if has("syntax error")
+            || has("no such table")
+            || has("no such column")
+            || has("has no column named")
+            || has("incomplete input")
+            || has("unrecognized token")
+        {
+            Some(Self::Syntax)
+        } else {
+            None
+        }
+    }
+}
+
+/// 已分类的 SQL 错误
+///
+/// ```rust
+/// use vtx_sdk::error::db::{DbError, DbErrorKind};
+///
+/// let err = DbError::parse("UNIQUE constraint failed: users.name").unwrap();
+/// assert_eq!(err.kind, DbErrorKind::UniqueViolation);
+/// assert_eq!(err.kind.status(), 409);
+///
+/// let err = DbError::parse("sql.busy: database is locked").unwrap();
+/// assert!(err.kind.is_retryable());
+///
+/// assert!(DbError::parse("disk I/O error").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbError {
+    pub kind: DbErrorKind,
+    pub message: String,
+}
+
+impl DbError {
+    pub fn new(kind: DbErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// 解析宿主 SQL 错误字符串（无法归类时返回 `None`）
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::from_host(&HostError::parse(raw))
+    }
+
+    /// 从已解析的宿主错误归类：优先使用错误码，其次匹配消息
+    pub fn from_host(host: &HostError) -> Option<Self> {
+        host.code
+            .as_deref()
+            .and_then(DbErrorKind::from_code)
+            .or_else(|| DbErrorKind::from_message(&host.message))
+            .map(|kind| Self::new(kind, host.message.clone()))
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.kind.code())
+    }
+}
@@ -0,0 +1,190 @@
+//! Structured parsing of host error messages.
+//!
+//! 宿主接口以 `string` 返回错误，本模块按以下优先级解析：
+//!
+//! 1. JSON 信封：`{"code": "not_found", "message": "UUID abc not found"}`
+//! 2. `code: message` 文法：`not_found: UUID abc not found`
+//!    - `code` 为 ASCII 字母开头，仅含字母、数字、`_`、`-`、`.` 的单个词（不含空格），后接 `": "`
+//!    - 带命名空间的错误码（如 `sql.unique_violation`）取最后一段匹配
+//!    - 未知错误码保留完整原始消息
+//! 3. 兜底：识别旧版宿主的固定措辞（`permission denied`、`not found`、`invalid event payload`），
+//!    其余消息一律视为 `Other`
+//!
+//! 未识别的错误码保留在 `HostError::code` 中，分类为 `HostErrorKind::Other`。
+
+use super::VtxError;
+
+/// 宿主错误分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostErrorKind {
+    /// 资源不存在（文件 UUID、记录等）
+    NotFound,
+    /// 权限不足或只读策略拒绝
+    PermissionDenied,
+    /// 参数不合法（如事件 payload 不是合法 JSON）
+    InvalidInput,
+    /// 约束冲突（唯一键、外键等）
+    Conflict,
+    /// 调用频率超限
+    RateLimited,
+    /// 操作超时
+    Timeout,
+    /// 依赖暂不可用（如数据库被锁定）
+    Unavailable,
+    /// 无法分类，由调用方决定映射
+    Other,
+}
+
+impl HostErrorKind {
+    /// 根据错误码分类（大小写不敏感，`-` 与 `_` 等价）
+    pub fn from_code(code: &str) -> Self {
+        let code = code.to_ascii_lowercase().replace('-', "_");
+        let code = code.rsplit('.').next().unwrap_or_default();
+
+        match code {
+            "not_found" | "no_such_file" | "enoent" => Self::NotFound,
+            "permission_denied" | "forbidden" | "read_only" | "readonly" => Self::PermissionDenied,
+            "invalid_argument" | "invalid_input" | "bad_request" | "invalid" => Self::InvalidInput,
+            "conflict"
+            | "already_exists"
+            | "constraint"
+            | "constraint_violation"
+            | "unique_violation"
+            | "foreign_key_violation"
+            | "not_null_violation" => Self::Conflict,
+            "rate_limited" | "too_many_requests" | "quota_exceeded" => Self::RateLimited,
+            "timeout" | "timed_out" | "deadline_exceeded" => Self::Timeout,
+            "unavailable" | "service_unavailable" | "busy" | "locked" => Self::Unavailable,
+            _ => Self::Other,
+        }
+    }
+
+    /// 按消息关键字分类（兜底，仅识别旧版宿主的固定措辞）
+    ///
+    /// 只匹配完整短语，避免 `invalid` / `timeout` 之类的单词误判普通消息；
+    /// 其余分类依赖结构化的错误码或 JSON 信封。
+    fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let has = |needle: &str| lower.contains(needle);
+
+        if has("permission denied") {
+            Self::PermissionDenied
+        } else if has("not found") {
+            Self::NotFound
+        } else if has("invalid event payload") {
+            Self::InvalidInput
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// 解析后的宿主错误
+///
+/// ```rust
+/// use vtx_sdk::error::host::{HostError, HostErrorKind};
+///
+/// let err = HostError::parse("not_found: UUID 42 does not exist");
+/// assert_eq!(err.kind, HostErrorKind::NotFound);
+/// assert_eq!(err.code.as_deref(), Some("not_found"));
+/// assert_eq!(err.message, "UUID 42 does not exist");
+///
+/// let err = HostError::parse(r#"{"code":"sql.unique_violation","message":"UNIQUE constraint failed: users.name"}"#);
+/// assert_eq!(err.kind, HostErrorKind::Conflict);
+///
+/// // 旧格式消息回退到关键字启发式
+/// let err = HostError::parse("Permission denied: database is read-only");
+/// assert_eq!(err.kind, HostErrorKind::PermissionDenied);
+/// assert_eq!(err.code, None);
+///
+/// // 未采用结构化格式的其他消息不做猜测
+/// let err = HostError::parse("transcode failed: invalid timeout value");
+/// assert_eq!(err.kind, HostErrorKind::Other);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostError {
+    pub kind: HostErrorKind,
+    /// 宿主提供的错误码（仅结构化格式可用）
+    pub code: Option<String>,
+    /// 错误消息（结构化格式下已去除错误码前缀）
+    pub message: String,
+}
+
+#[derive(serde::Deserialize)]
+struct Envelope {
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl HostError {
+    /// 解析宿主错误字符串
+    pub fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim();
+
+        if trimmed.starts_with('{') {
+            if let Ok(envelope) = serde_json::from_str::<Envelope>(trimmed) {
+                return Self::structured(envelope.code, envelope.message);
+            }
+        }
+
+        if let Some((code, message)) = trimmed.split_once(": ") {
+            if is_code(code) {
+                return match HostErrorKind::from_code(code) {
+                    // 未知错误码（如工具名前缀）保留完整消息，避免丢失上下文
+                    HostErrorKind::Other => Self {
+                        kind: HostErrorKind::from_message(message),
+                        code: Some(code.to_string()),
+                        message: raw.to_string(),
+                    },
+                    kind => Self {
+                        kind,
+                        code: Some(code.to_string()),
+                        message: message.trim().to_string(),
+                    },
+                };
+            }
+        }
+
+        Self {
+            kind: HostErrorKind::from_message(trimmed),
+            code: None,
+            message: raw.to_string(),
+        }
+    }
+
+    fn structured(code: String, message: String) -> Self {
+        let kind = match HostErrorKind::from_code(&code) {
+            // 未知错误码时仍尝试从消息中识别
+            HostErrorKind::Other => HostErrorKind::from_message(&message),
+            kind => kind,
+        };
+        Self {
+            kind,
+            code: Some(code),
+            message,
+        }
+    }
+
+    /// 转换为 `VtxError`；`Other` 交由 `fallback` 决定（如数据库上下文使用 `DatabaseError`）
+    pub fn into_vtx_error(self, fallback: impl FnOnce(String) -> VtxError) -> VtxError {
+        let msg = self.message;
+        match self.kind {
+            HostErrorKind::NotFound => VtxError::NotFound(msg),
+            HostErrorKind::PermissionDenied => VtxError::PermissionDenied(msg),
+            HostErrorKind::InvalidInput => VtxError::BadRequest(msg),
+            HostErrorKind::Conflict => VtxError::Conflict(msg),
+            HostErrorKind::RateLimited => VtxError::RateLimited(msg),
+            HostErrorKind::Timeout => VtxError::Timeout(msg),
+            HostErrorKind::Unavailable => VtxError::Unavailable(msg),
+            HostErrorKind::Other => fallback(msg),
+        }
+    }
+}
+
+/// `code: message` 中的错误码：字母开头的单个词
+fn is_code(code: &str) -> bool {
+    let mut chars = code.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub mod db;
+pub mod host;
+
+use db::DbError;
+use host::HostError;
+
+/// 插件运行时错误类型（统一错误模型）
+///
+/// 定义插件在运行过程中可能出现的所有已知错误。
+/// 所有错误均支持序列化，可用于 HTTP 返回或日志透传。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum VtxError {
+    /// 底层数据库错误（无法归类的 SQL 执行失败）
+    DatabaseError(String),
+
+    /// 已归类的数据库错误（约束冲突、锁定、语法错误等，见 `error::db`）
+    Db(DbError),
+
+    /// 序列化或反序列化失败（如 JSON 格式不匹配）
+    ///
+    /// 映射为 400，用于客户端提交的数据；响应序列化与查询结果解码失败属于服务端错误，
+    /// 分别为 500 `serialization_error`（`http::response_serialization_error`）与 `Internal`。
+    SerializationError(String),
+
+    /// 身份验证失败（带建议返回的 HTTP 状态码，如 401 / 403）
+    AuthDenied(u16),
+
+    /// 权限不足（如在只读环境尝试执行写操作）
+    PermissionDenied(String),
+
+    /// 资源不存在（如文件、视频、用户未找到等）
+    NotFound(String),
+
+    /// 插件内部逻辑错误（兜底类型）
+    Internal(String),
+
+    /// 请求参数不合法（缺失字段、格式错误等）
+    BadRequest(String),
+
+    /// 资源状态冲突（如唯一键重复、版本不一致）
+    Conflict(String),
+
+    /// 请求过于频繁
+    RateLimited(String),
+
+    /// 依赖操作超时（如出站 HTTP、FFmpeg 转码）
+    Timeout(String),
+
+    /// 依赖服务暂不可用
+    Unavailable(String),
+
+    /// 携带详细信息（detail / 字段错误 / 扩展成员）的错误
+    ///
+    /// 通常由 `with_detail` / `with_field_error` 或 `From<ProblemDetails>` 构造。
+    Problem(Box<ProblemDetails>),
+}
+
+impl VtxError {
+    /// 稳定的机器可读错误码
+    ///
+    /// | 变体 | 错误码 | HTTP 状态码 |
+    /// |------|--------|-------------|
+    /// | `BadRequest` | `bad_request` | 400 |
+    /// | `SerializationError` | `serialization_error` | 400 |
+    /// | `AuthDenied(code)` | `auth_denied` | `code` |
+    /// | `PermissionDenied` | `permission_denied` | 403 |
+    /// | `NotFound` | `not_found` | 404 |
+    /// | `Conflict` | `conflict` | 409 |
+    /// | `RateLimited` | `rate_limited` | 429 |
+    /// | `DatabaseError` | `database_error` | 500 |
+    /// | `Db(err)` | `err.kind.code()` | `err.kind.status()` |
+    /// | `Internal` | `internal` | 500 |
+    /// | `Unavailable` | `unavailable` | 503 |
+    /// | `Timeout` | `timeout` | 504 |
+    /// | `Problem` | 自定义 | 自定义 |
+    pub fn code(&self) -> &str {
+        match self {
+            VtxError::DatabaseError(_) => "database_error",
+            VtxError::Db(err) => err.kind.code(),
+            VtxError::SerializationError(_) => "serialization_error",
+            VtxError::AuthDenied(_) => "auth_denied",
+            VtxError::PermissionDenied(_) => "permission_denied",
+            VtxError::NotFound(_) => "not_found",
+            VtxError::Internal(_) => "internal",
+            VtxError::BadRequest(_) => "bad_request",
+            VtxError::Conflict(_) => "conflict",
+            VtxError::RateLimited(_) => "rate_limited",
+            VtxError::Timeout(_) => "timeout",
+            VtxError::Unavailable(_) => "unavailable",
+            VtxError::Problem(problem) => &problem.code,
+        }
+    }
+
+    /// 对应的 HTTP 状态码（见 `code` 的映射表）
+    pub fn status(&self) -> u16 {
+        match self {
+            VtxError::AuthDenied(code) => *code,
+            VtxError::Db(err) => err.kind.status(),
+            VtxError::BadRequest(_) | VtxError::SerializationError(_) => 400,
+            VtxError::PermissionDenied(_) => 403,
+            VtxError::NotFound(_) => 404,
+            VtxError::Conflict(_) => 409,
+            VtxError::RateLimited(_) => 429,
+            VtxError::DatabaseError(_) | VtxError::Internal(_) => 500,
+            VtxError::Unavailable(_) => 503,
+            VtxError::Timeout(_) => 504,
+            VtxError::Problem(problem) => problem.status,
+        }
+    }
+
+    /// 转换为 RFC 7807 问题详情
+    pub fn to_problem(&self) -> ProblemDetails {
+        let detail = match self {
+            VtxError::AuthDenied(_) => None,
+            VtxError::Problem(problem) => return (**problem).clone(),
+            VtxError::Db(err) => Some(err.message.clone()),
+            VtxError::DatabaseError(msg)
+            | VtxError::SerializationError(msg)
+            | VtxError::PermissionDenied(msg)
+            | VtxError::NotFound(msg)
+            | VtxError::Internal(msg)
+            | VtxError::BadRequest(msg)
+            | VtxError::Conflict(msg)
+            | VtxError::RateLimited(msg)
+            | VtxError::Timeout(msg)
+            | VtxError::Unavailable(msg) => Some(msg.clone()),
+        };
+
+        let problem = ProblemDetails::new(self.status(), self.code());
+        match detail {
+            Some(detail) => problem.detail(detail),
+            None => problem,
+        }
+    }
+
+    /// 附加（或替换）详细说明，保留原有错误码与状态码
+    pub fn with_detail(self, detail: impl Into<String>) -> Self {
+        VtxError::Problem(Box::new(self.into_problem().detail(detail)))
+    }
+
+    /// 附加一条字段级错误，保留原有错误码与状态码
+    ///
+    /// ```rust
+    /// use vtx_sdk::error::VtxError;
+    ///
+    /// let err = VtxError::BadRequest("invalid video".into())
+    ///     .with_field_error("title", "must not be empty")
+    ///     .with_field_error("duration", "must be positive");
+    ///
+    /// assert_eq!(err.code(), "bad_request");
+    /// assert_eq!(err.status(), 400);
+    /// assert_eq!(err.to_problem().errors.len(), 2);
+    /// ```
+    pub fn with_field_error(self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        VtxError::Problem(Box::new(self.into_problem().field_error(field, message)))
+    }
+
+    /// 已归类的数据库错误（仅 `Db` 变体）
+    pub fn db_error(&self) -> Option<&DbError> {
+        match self {
+            VtxError::Db(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// 稍后重试可能成功（数据库繁忙、依赖暂不可用、超时、限流）
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            VtxError::Db(err) => err.kind.is_retryable(),
+            VtxError::Unavailable(_) | VtxError::Timeout(_) | VtxError::RateLimited(_) => true,
+            VtxError::Problem(problem) => matches!(problem.status, 429 | 503 | 504),
+            _ => false,
+        }
+    }
+
+    fn into_problem(self) -> ProblemDetails {
+        match self {
+            VtxError::Problem(problem) => *problem,
+            other => other.to_problem(),
+        }
+    }
+
+    /// 将宿主侧返回的 `string` 错误消息分类映射（解析规则见 `error::host`）
+    ///
+    /// 无法分类的消息映射为 `Internal`。
+    pub fn from_host_message(message: impl Into<String>) -> Self {
+        HostError::parse(&message.into()).into_vtx_error(VtxError::Internal)
+    }
+}
+
+impl fmt::Display for VtxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VtxError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+            VtxError::Db(err) => write!(f, "Database error: {}", err),
+            VtxError::SerializationError(msg) => write!(f, "Data serialization error: {}", msg),
+            VtxError::AuthDenied(code) => write!(f, "Authentication denied (Code: {})", code),
+            VtxError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
+            VtxError::NotFound(msg) => write!(f, "Resource not found: {}", msg),
+            VtxError::Internal(msg) => write!(f, "Internal error: {}", msg),
+            VtxError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            VtxError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            VtxError::RateLimited(msg) => write!(f, "Rate limited: {}", msg),
+            VtxError::Timeout(msg) => write!(f, "Timeout: {}", msg),
+            VtxError::Unavailable(msg) => write!(f, "Service unavailable: {}", msg),
+            VtxError::Problem(problem) => match &problem.detail {
+                Some(detail) => write!(f, "{}: {}", problem.title, detail),
+                None => f.write_str(&problem.title),
+            },
+        }
+    }
+}
+
+impl std::error::Error for VtxError {}
+
+impl From<DbError> for VtxError {
+    fn from(err: DbError) -> Self {
+        VtxError::Db(err)
+    }
+}
+
+impl From<ProblemDetails> for VtxError {
+    fn from(problem: ProblemDetails) -> Self {
+        VtxError::Problem(Box::new(problem))
+    }
+}
+
+/// 字段级错误（用于表单 / JSON 校验）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// RFC 7807 问题详情（`application/problem+json`）
+///
+/// 标准成员之外，附带扩展成员 `code`（稳定错误码）与 `errors`（字段错误，可选）。
+///
+/// ```json
+/// {
+///   "type": "about:blank",
+///   "title": "Bad Request",
+///   "status": 400,
+///   "detail": "invalid video",
+///   "code": "bad_request",
+///   "errors": [{ "field": "title", "message": "must not be empty" }]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    /// 其他扩展成员
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ProblemDetails {
+    /// 以状态码与错误码创建（`type` 为 `about:blank`，`title` 为状态码短语）
+    pub fn new(status: u16, code: impl Into<String>) -> Self {
+        Self {
+            type_uri: "about:blank".to_string(),
+            title: reason_phrase(status).to_string(),
+            status,
+            detail: None,
+            instance: None,
+            code: code.into(),
+            errors: Vec::new(),
+            extensions: serde_json::Map::new(),
+        }
+    }
+
+    /// 问题类型 URI（设置后建议同时提供专用 `title`）
+    pub fn type_uri(mut self, uri: impl Into<String>) -> Self {
+        self.type_uri = uri.into();
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    pub fn field_error(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+        self
+    }
+
+    /// 添加扩展成员（与标准成员同名时被忽略）
+    pub fn extension(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        const RESERVED: [&str; 7] = [
+            "type", "title", "status", "detail", "instance", "code", "errors",
+        ];
+
+        let key = key.into();
+        if !RESERVED.contains(&key.as_str()) {
+            let value = serde_json::to_value(value).unwrap_or(serde_json::Value::Null);
+            self.extensions.insert(key, value);
+        }
+        self
+    }
+}
+
+/// 常见 HTTP 状态码的标准短语
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        412 => "Precondition Failed",
+        413 => "Content Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Content",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ if status < 500 => "Client Error",
+        _ => "Server Error",
+    }
+}
+
+/// 插件标准结果类型别名
+///
+/// 推荐用于所有返回 VtxError 的接口中，确保错误链统一。
+pub type VtxResult<T> = Result<T, VtxError>;
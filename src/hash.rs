@@ -0,0 +1,14 @@
+//! Non-cryptographic hashing shared by cache validators and migration checksums.
+
+/// FNV-1a 64 位哈希
+///
+/// 结果在不同构建、平台与宿主之间保持稳定（不同于 `std::hash`），
+/// 仅用于内容指纹，不具备抗碰撞的安全属性。
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(OFFSET, |h, b| (h ^ u64::from(*b)).wrapping_mul(PRIME))
+}
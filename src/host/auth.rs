@@ -32,6 +32,7 @@ impl<'a> AuthRequest<'a> {
     ///
     /// 行为：
     /// 若 Header 不存在，返回 `AuthDenied(401)` 错误。
+    #[allow(clippy::unnecessary_lazy_evaluations)]
     pub fn require_header(&self, key: &str) -> VtxResult<&str> {
         self.header(key).ok_or_else(|| {
             // 提示：具体缺失哪个 Header 的信息在转换为 u16 时会丢失，
@@ -56,6 +57,7 @@ impl<'a> AuthRequest<'a> {
     ///
     /// 行为：
     /// 若 Authorization 头缺失或格式不正确，返回 `AuthDenied(401)`。
+    #[allow(clippy::unnecessary_lazy_evaluations)]
     pub fn require_bearer_token(&self) -> VtxResult<&str> {
         self.bearer_token().ok_or_else(|| VtxError::AuthDenied(401))
     }
@@ -140,13 +142,9 @@ impl IntoAuthResult for VtxResult<UserContext> {
             Err(e) => {
                 // 错误降级策略：将丰富的错误类型映射为 HTTP 状态码
                 let status_code = match e {
-                    VtxError::AuthDenied(code) => code,
-                    VtxError::PermissionDenied(_) => 403,
-                    VtxError::NotFound(_) => 404,
-                    // 数据库错误、序列化错误或内部错误，统一视为 500
-                    VtxError::DatabaseError(_)
-                    | VtxError::SerializationError(_)
-                    | VtxError::Internal(_) => 500,
+                    // 鉴权阶段没有请求体，序列化失败只可能来自插件自身，视为 500
+                    VtxError::SerializationError(_) => 500,
+                    other => other.status(),
                 };
                 Err(status_code)
             }
@@ -1,6 +1,7 @@
 //! Host-side context helpers.
 
-use crate::bindings::vtx::api::{auth_types::CurrentUser, context};
+use crate::bindings::vtx::api::auth_types::CurrentUser;
+use crate::imports::context;
 
 pub type CurrentUserInfo = CurrentUser;
 
@@ -0,0 +1,121 @@
+//! Reusable statements and batch execution (`Statement`, `execute_batch`).
+//!
+//! 宿主 SQL 接口没有预编译语句句柄，每组参数仍是一次 `sql::execute` 调用；
+//! 批量执行的主要收益来自隐式事务（SQLite 无需为每行单独提交）以及统一的失败定位。
+
+use super::{transaction, DbValue, TryToDbValue};
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// 批量执行失败：`index` 为第一个失败的参数组序号（从 0 开始）
+///
+/// 事务本身开启或提交失败时 `index` 为 `None`。批量执行失败时已执行的行全部回滚。
+#[derive(Debug, Clone)]
+pub struct BatchError {
+    pub index: Option<usize>,
+    pub error: VtxError,
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "Batch row {} failed: {}", index, self.error),
+            None => write!(f, "Batch failed: {}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+/// 转换为 `VtxError` 时保留原错误码与状态码，失败序号记录在 `batch_index` 扩展字段
+impl From<BatchError> for VtxError {
+    fn from(err: BatchError) -> Self {
+        match err.index {
+            Some(index) => VtxError::Problem(Box::new(
+                err.error.to_problem().extension("batch_index", index),
+            )),
+            None => err.error,
+        }
+    }
+}
+
+/// 可重复执行的 SQL 语句（由 `db::prepare` 创建）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    sql: String,
+}
+
+/// 创建可复用的语句
+///
+/// ```rust,no_run
+/// use vtx_sdk::db::{self, ToDbValue};
+///
+/// let insert = db::prepare("INSERT INTO tags (video_id, tag) VALUES (?, ?)");
+/// let counts = insert.execute_batch(
+///     ["rust", "wasm"]
+///         .iter()
+///         .map(|tag| vec![7.to_db_value(), tag.to_db_value()]),
+/// )?;
+/// assert_eq!(counts, vec![1, 1]);
+/// # Ok::<(), vtx_sdk::error::VtxError>(())
+/// ```
+pub fn prepare(sql: &str) -> Statement {
+    Statement {
+        sql: sql.to_string(),
+    }
+}
+
+impl Statement {
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// 以一组参数执行（同 `db::execute`）
+    pub fn execute(&self, params: &[&dyn TryToDbValue]) -> VtxResult<u64> {
+        super::execute(&self.sql, params)
+    }
+
+    /// 以一组参数查询（同 `db::query`）
+    pub fn query<T: DeserializeOwned>(&self, params: &[&dyn TryToDbValue]) -> VtxResult<Vec<T>> {
+        super::query(&self.sql, params)
+    }
+
+    /// 批量执行（同 `db::execute_batch`）
+    pub fn execute_batch<I>(&self, rows: I) -> Result<Vec<u64>, BatchError>
+    where
+        I: IntoIterator<Item = Vec<DbValue>>,
+    {
+        execute_batch(&self.sql, rows)
+    }
+}
+
+/// 以多组参数重复执行同一条 SQL，返回每组参数的影响行数
+///
+/// 全部执行包裹在一个事务中（已在事务内时使用保存点），任一组失败即停止并整体回滚，
+/// 错误中携带失败的参数组序号。
+pub fn execute_batch<I>(sql: &str, rows: I) -> Result<Vec<u64>, BatchError>
+where
+    I: IntoIterator<Item = Vec<DbValue>>,
+{
+    let mut failed = None;
+    transaction(|tx| {
+        let mut counts = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let params: Vec<&dyn TryToDbValue> =
+                row.iter().map(|p| p as &dyn TryToDbValue).collect();
+            match tx.execute(sql, &params) {
+                Ok(count) => counts.push(count),
+                Err(err) => {
+                    failed = Some(index);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(counts)
+    })
+    .map_err(|error| BatchError {
+        index: failed,
+        error,
+    })
+}
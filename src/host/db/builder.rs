@@ -0,0 +1,611 @@
+//! SQLite-dialect query builder producing SQL text plus bound parameters.
+//!
+//! 构造器只负责拼接 SQL 与参数列表，执行仍通过 `db::execute` / `db::query`，
+//! 因此可以在原生单元测试中直接断言生成的 SQL：
+//!
+//! ```rust
+//! use vtx_sdk::db::{self, Order};
+//!
+//! let q = db::select("videos")
+//!     .columns(&["id", "title"])
+//!     .where_eq("owner_id", "u1")
+//!     .where_gt("duration", 60)
+//!     .order_by("created_at", Order::Desc)
+//!     .limit(20)
+//!     .offset(40)
+//!     .build();
+//!
+//! assert_eq!(
+//!     q.sql,
+//!     r#"SELECT "id", "title" FROM "videos" WHERE "owner_id" = ? AND "duration" > ? ORDER BY "created_at" DESC LIMIT 20 OFFSET 40"#
+//! );
+//! assert_eq!(q.params.len(), 2);
+//! ```
+//!
+//! 标识符统一使用双引号转义（`table.column` 按段转义），值一律以 `?` 占位绑定，
+//! 占位符数量与参数列表始终一致。
+//!
+//! 参数在添加时经 `TryToDbValue::try_to_db_value` 转换；转换失败（如 `u64` 溢出）时
+//! 该位置以 NULL 占位，错误在 `execute` / `fetch` 时返回，不会执行语句。
+
+use super::{DbRow, DbValue, TryToDbValue};
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+
+/// 生成的 SQL 语句及其参数（按占位符顺序）
+#[derive(Debug, Clone)]
+pub struct BuiltQuery {
+    pub sql: String,
+    pub params: Vec<DbValue>,
+    error: Option<VtxError>,
+}
+
+impl BuiltQuery {
+    /// 构造是否成功（参数转换失败或语句不完整时返回首个错误，如未调用 `set` 的 `UPDATE`）
+    pub fn check(&self) -> VtxResult<()> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// 以 `db::execute` 执行，返回影响行数
+    pub fn execute(&self) -> VtxResult<u64> {
+        self.check()?;
+        super::execute(&self.sql, &self.param_refs())
+    }
+
+    /// 以 `db::query` 执行，返回反序列化后的结果集
+    pub fn fetch<T: DeserializeOwned>(&self) -> VtxResult<Vec<T>> {
+        self.check()?;
+        super::query(&self.sql, &self.param_refs())
+    }
+
+    /// 以 `db::query_as` 执行，按 `DbRow` 映射每一行
+    pub fn fetch_as<T: DbRow>(&self) -> VtxResult<Vec<T>> {
+        self.check()?;
+        super::query_as(&self.sql, &self.param_refs())
+    }
+
+    fn param_refs(&self) -> Vec<&dyn TryToDbValue> {
+        self.params.iter().map(|p| p as &dyn TryToDbValue).collect()
+    }
+}
+
+/// 转换单个参数；失败时记录首个错误并以 NULL 占位
+fn bind(value: &dyn TryToDbValue, error: &mut Option<VtxError>) -> DbValue {
+    value.try_to_db_value().unwrap_or_else(|err| {
+        error.get_or_insert(err);
+        DbValue::NullVal
+    })
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// 转义标识符：`videos` → `"videos"`，`v.id` → `"v"."id"`，`*` 保持不变
+pub(super) fn quote_ident(ident: &str) -> String {
+    ident
+        .split('.')
+        .map(|part| match part {
+            "*" => "*".to_string(),
+            _ => format!("\"{}\"", part.replace('"', "\"\"")),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn quote_list(idents: &[&str]) -> String {
+    idents
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// WHERE 条件（以 AND 连接）
+#[derive(Debug, Clone, Default)]
+struct Conditions {
+    clauses: Vec<String>,
+    params: Vec<DbValue>,
+    error: Option<VtxError>,
+}
+
+impl Conditions {
+    fn compare(&mut self, column: &str, op: &str, value: &dyn TryToDbValue) {
+        self.clauses
+            .push(format!("{} {} ?", quote_ident(column), op));
+        let value = bind(value, &mut self.error);
+        self.params.push(value);
+    }
+
+    fn write(&self, sql: &mut String, params: &mut Vec<DbValue>) {
+        if !self.clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.clauses.join(" AND "));
+            params.extend(self.params.iter().cloned());
+        }
+    }
+}
+
+/// 为带 WHERE 子句的构造器生成条件方法
+macro_rules! where_methods {
+    () => {
+        /// `column = ?`
+        pub fn where_eq(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, "=", &value);
+            self
+        }
+
+        /// `column <> ?`
+        pub fn where_ne(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, "<>", &value);
+            self
+        }
+
+        /// `column < ?`
+        pub fn where_lt(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, "<", &value);
+            self
+        }
+
+        /// `column <= ?`
+        pub fn where_le(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, "<=", &value);
+            self
+        }
+
+        /// `column > ?`
+        pub fn where_gt(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, ">", &value);
+            self
+        }
+
+        /// `column >= ?`
+        pub fn where_ge(mut self, column: &str, value: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, ">=", &value);
+            self
+        }
+
+        /// `column LIKE ?`
+        pub fn where_like(mut self, column: &str, pattern: impl TryToDbValue) -> Self {
+            self.conditions.compare(column, "LIKE", &pattern);
+            self
+        }
+
+        /// `column IS NULL`
+        pub fn where_null(mut self, column: &str) -> Self {
+            self.conditions
+                .clauses
+                .push(format!("{} IS NULL", quote_ident(column)));
+            self
+        }
+
+        /// `column IS NOT NULL`
+        pub fn where_not_null(mut self, column: &str) -> Self {
+            self.conditions
+                .clauses
+                .push(format!("{} IS NOT NULL", quote_ident(column)));
+            self
+        }
+
+        /// `column IN (?, ?, ...)`（空列表生成 SQLite 合法的 `IN ()`，不匹配任何行）
+        pub fn where_in<V: TryToDbValue>(
+            mut self,
+            column: &str,
+            values: impl IntoIterator<Item = V>,
+        ) -> Self {
+            let error = &mut self.conditions.error;
+            let values: Vec<DbValue> = values.into_iter().map(|v| bind(&v, error)).collect();
+            let placeholders = vec!["?"; values.len()].join(", ");
+            self.conditions
+                .clauses
+                .push(format!("{} IN ({})", quote_ident(column), placeholders));
+            self.conditions.params.extend(values);
+            self
+        }
+
+        /// 原始条件片段（不做转义）
+        ///
+        /// 片段中 `?` 的数量（字符串字面量与注释中的除外）必须与 `params` 一致，
+        /// 否则记录 `Internal` 错误，执行前返回，避免后续参数整体错位。
+        pub fn where_raw(mut self, clause: &str, params: &[&dyn TryToDbValue]) -> Self {
+            let placeholders = super::named::count_placeholders(clause);
+            if placeholders != params.len() {
+                self.conditions
+                    .error
+                    .get_or_insert(VtxError::Internal(format!(
+                        "where_raw clause '{}' has {} placeholder(s) but {} param(s)",
+                        clause,
+                        placeholders,
+                        params.len()
+                    )));
+            }
+            self.conditions.clauses.push(format!("({})", clause));
+            for p in params {
+                let value = bind(*p, &mut self.conditions.error);
+                self.conditions.params.push(value);
+            }
+            self
+        }
+    };
+}
+
+fn write_returning(sql: &mut String, returning: &[String]) {
+    if !returning.is_empty() {
+        let cols: Vec<&str> = returning.iter().map(String::as_str).collect();
+        sql.push_str(" RETURNING ");
+        sql.push_str(&quote_list(&cols));
+    }
+}
+
+// =====================
+// SELECT
+// =====================
+
+/// `SELECT` 构造器（由 `db::select` 创建）
+#[derive(Debug, Clone)]
+pub struct Select {
+    table: String,
+    columns: Vec<String>,
+    conditions: Conditions,
+    order: Vec<(String, Order)>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+/// 开始构造 `SELECT`（默认 `SELECT *`）
+pub fn select(table: &str) -> Select {
+    Select {
+        table: table.to_string(),
+        columns: Vec::new(),
+        conditions: Conditions::default(),
+        order: Vec::new(),
+        limit: None,
+        offset: None,
+    }
+}
+
+impl Select {
+    /// 选择列（标识符会被转义）
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns.extend(columns.iter().map(|c| quote_ident(c)));
+        self
+    }
+
+    /// 原始列表达式（如 `COUNT(*) AS total`，不做转义）
+    pub fn column_raw(mut self, expr: &str) -> Self {
+        self.columns.push(expr.to_string());
+        self
+    }
+
+    where_methods!();
+
+    pub fn order_by(mut self, column: &str, order: Order) -> Self {
+        self.order.push((column.to_string(), order));
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 跳过的行数（未设置 `limit` 时自动补充 `LIMIT -1`，满足 SQLite 语法要求）
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn build(&self) -> BuiltQuery {
+        let columns = if self.columns.is_empty() {
+            "*".to_string()
+        } else {
+            self.columns.join(", ")
+        };
+
+        let mut sql = format!("SELECT {} FROM {}", columns, quote_ident(&self.table));
+        let mut params = Vec::new();
+        self.conditions.write(&mut sql, &mut params);
+
+        if !self.order.is_empty() {
+            let order: Vec<String> = self
+                .order
+                .iter()
+                .map(|(col, dir)| {
+                    let dir = match dir {
+                        Order::Asc => "ASC",
+                        Order::Desc => "DESC",
+                    };
+                    format!("{} {}", quote_ident(col), dir)
+                })
+                .collect();
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order.join(", "));
+        }
+
+        match (self.limit, self.offset) {
+            (Some(limit), _) => sql.push_str(&format!(" LIMIT {}", limit)),
+            (None, Some(_)) => sql.push_str(" LIMIT -1"),
+            (None, None) => {}
+        }
+        if let Some(offset) = self.offset {
+            sql.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        BuiltQuery {
+            sql,
+            params,
+            error: self.conditions.error.clone(),
+        }
+    }
+
+    /// 执行查询
+    pub fn fetch<T: DeserializeOwned>(&self) -> VtxResult<Vec<T>> {
+        self.build().fetch()
+    }
+}
+
+// =====================
+// INSERT
+// =====================
+
+/// `INSERT` 构造器（由 `db::insert` 创建）
+#[derive(Debug, Clone)]
+pub struct Insert {
+    table: String,
+    columns: Vec<String>,
+    params: Vec<DbValue>,
+    error: Option<VtxError>,
+    returning: Vec<String>,
+}
+
+/// 开始构造 `INSERT`
+///
+/// ```rust
+/// use vtx_sdk::db;
+///
+/// let q = db::insert("videos")
+///     .value("title", "intro")
+///     .value("duration", 120)
+///     .returning(&["id"])
+///     .build();
+///
+/// assert_eq!(
+///     q.sql,
+///     r#"INSERT INTO "videos" ("title", "duration") VALUES (?, ?) RETURNING "id""#
+/// );
+/// ```
+pub fn insert(table: &str) -> Insert {
+    Insert {
+        table: table.to_string(),
+        columns: Vec::new(),
+        params: Vec::new(),
+        error: None,
+        returning: Vec::new(),
+    }
+}
+
+impl Insert {
+    /// 设置一列的值
+    pub fn value(mut self, column: &str, value: impl TryToDbValue) -> Self {
+        self.columns.push(column.to_string());
+        let value = bind(&value, &mut self.error);
+        self.params.push(value);
+        self
+    }
+
+    /// `RETURNING` 列（需通过 `fetch` 读取）
+    ///
+    /// ⚠️ `fetch` 经宿主查询接口（`db::query`）执行写语句：该接口约定仅用于 SELECT，
+    /// 严格的宿主会拒绝，Restricted 安全策略下也不应依赖；可移植代码请使用 `execute`
+    /// 并另行查询所需的列。
+    pub fn returning(mut self, columns: &[&str]) -> Self {
+        self.returning.extend(columns.iter().map(|c| c.to_string()));
+        self
+    }
+
+    pub fn build(&self) -> BuiltQuery {
+        let mut sql = if self.columns.is_empty() {
+            format!("INSERT INTO {} DEFAULT VALUES", quote_ident(&self.table))
+        } else {
+            let cols: Vec<&str> = self.columns.iter().map(String::as_str).collect();
+            format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                quote_ident(&self.table),
+                quote_list(&cols),
+                vec!["?"; cols.len()].join(", ")
+            )
+        };
+        write_returning(&mut sql, &self.returning);
+
+        BuiltQuery {
+            sql,
+            params: self.params.clone(),
+            error: self.error.clone(),
+        }
+    }
+
+    /// 执行并返回影响行数
+    pub fn execute(&self) -> VtxResult<u64> {
+        self.build().execute()
+    }
+
+    /// 执行并读取 `RETURNING` 结果（经宿主查询接口执行，限制见 `returning`）
+    pub fn fetch<T: DeserializeOwned>(&self) -> VtxResult<Vec<T>> {
+        self.build().fetch()
+    }
+}
+
+// =====================
+// UPDATE
+// =====================
+
+/// `UPDATE` 构造器（由 `db::update` 创建）
+#[derive(Debug, Clone)]
+pub struct Update {
+    table: String,
+    assignments: Vec<String>,
+    params: Vec<DbValue>,
+    error: Option<VtxError>,
+    conditions: Conditions,
+    returning: Vec<String>,
+}
+
+/// 开始构造 `UPDATE`
+///
+/// ```rust
+/// use vtx_sdk::db;
+///
+/// let q = db::update("videos")
+///     .set("title", "renamed")
+///     .where_eq("id", 7)
+///     .build();
+///
+/// assert_eq!(q.sql, r#"UPDATE "videos" SET "title" = ? WHERE "id" = ?"#);
+/// assert_eq!(q.params.len(), 2);
+///
+/// // 没有任何赋值时不会生成 `UPDATE ... SET ` 这样的残缺语句，而是在执行前报错
+/// assert!(db::update("videos").where_eq("id", 7).build().check().is_err());
+/// ```
+pub fn update(table: &str) -> Update {
+    Update {
+        table: table.to_string(),
+        assignments: Vec::new(),
+        params: Vec::new(),
+        error: None,
+        conditions: Conditions::default(),
+        returning: Vec::new(),
+    }
+}
+
+impl Update {
+    /// `column = ?`
+    pub fn set(mut self, column: &str, value: impl TryToDbValue) -> Self {
+        self.assignments
+            .push(format!("{} = ?", quote_ident(column)));
+        let value = bind(&value, &mut self.error);
+        self.params.push(value);
+        self
+    }
+
+    /// 原始赋值表达式（如 `views = views + 1`，不做转义）
+    pub fn set_raw(mut self, expr: &str) -> Self {
+        self.assignments.push(expr.to_string());
+        self
+    }
+
+    where_methods!();
+
+    /// `RETURNING` 列（需通过 `fetch` 读取）
+    ///
+    /// ⚠️ `fetch` 经宿主查询接口（`db::query`）执行写语句：该接口约定仅用于 SELECT，
+    /// 严格的宿主会拒绝，Restricted 安全策略下也不应依赖；可移植代码请使用 `execute`
+    /// 并另行查询所需的列。
+    pub fn returning(mut self, columns: &[&str]) -> Self {
+        self.returning.extend(columns.iter().map(|c| c.to_string()));
+        self
+    }
+
+    pub fn build(&self) -> BuiltQuery {
+        let mut sql = format!(
+            "UPDATE {} SET {}",
+            quote_ident(&self.table),
+            self.assignments.join(", ")
+        );
+        let mut params = self.params.clone();
+        self.conditions.write(&mut sql, &mut params);
+        write_returning(&mut sql, &self.returning);
+
+        let error = if self.assignments.is_empty() {
+            Some(VtxError::Internal(format!(
+                "UPDATE {} has no SET assignments",
+                quote_ident(&self.table)
+            )))
+        } else {
+            None
+        };
+
+        BuiltQuery {
+            sql,
+            params,
+            error: error
+                .or_else(|| self.error.clone())
+                .or_else(|| self.conditions.error.clone()),
+        }
+    }
+
+    /// 执行并返回影响行数
+    pub fn execute(&self) -> VtxResult<u64> {
+        self.build().execute()
+    }
+
+    /// 执行并读取 `RETURNING` 结果（经宿主查询接口执行，限制见 `returning`）
+    pub fn fetch<T: DeserializeOwned>(&self) -> VtxResult<Vec<T>> {
+        self.build().fetch()
+    }
+}
+
+// =====================
+// DELETE
+// =====================
+
+/// `DELETE` 构造器（由 `db::delete` 创建）
+#[derive(Debug, Clone)]
+pub struct Delete {
+    table: String,
+    conditions: Conditions,
+    returning: Vec<String>,
+}
+
+/// 开始构造 `DELETE`
+///
+/// ⚠️ 未添加任何条件时会删除整表数据。
+pub fn delete(table: &str) -> Delete {
+    Delete {
+        table: table.to_string(),
+        conditions: Conditions::default(),
+        returning: Vec::new(),
+    }
+}
+
+impl Delete {
+    where_methods!();
+
+    /// `RETURNING` 列（需通过 `fetch` 读取）
+    ///
+    /// ⚠️ `fetch` 经宿主查询接口（`db::query`）执行写语句：该接口约定仅用于 SELECT，
+    /// 严格的宿主会拒绝，Restricted 安全策略下也不应依赖；可移植代码请使用 `execute`
+    /// 并另行查询所需的列。
+    pub fn returning(mut self, columns: &[&str]) -> Self {
+        self.returning.extend(columns.iter().map(|c| c.to_string()));
+        self
+    }
+
+    pub fn build(&self) -> BuiltQuery {
+        let mut sql = format!("DELETE FROM {}", quote_ident(&self.table));
+        let mut params = Vec::new();
+        self.conditions.write(&mut sql, &mut params);
+        write_returning(&mut sql, &self.returning);
+
+        BuiltQuery {
+            sql,
+            params,
+            error: self.conditions.error.clone(),
+        }
+    }
+
+    /// 执行并返回影响行数
+    pub fn execute(&self) -> VtxResult<u64> {
+        self.build().execute()
+    }
+
+    /// 执行并读取 `RETURNING` 结果（经宿主查询接口执行，限制见 `returning`）
+    pub fn fetch<T: DeserializeOwned>(&self) -> VtxResult<Vec<T>> {
+        self.build().fetch()
+    }
+}
@@ -0,0 +1,175 @@
+//! Host-side SQL helpers.
+
+use crate::error::host::HostError;
+use crate::error::{VtxError, VtxResult};
+use crate::imports::sql;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+mod batch;
+mod builder;
+mod named;
+mod pages;
+mod row;
+mod table;
+mod transaction;
+mod value;
+
+pub use crate::params;
+pub use batch::{execute_batch, prepare, BatchError, Statement};
+pub use builder::{
+    delete, insert, select, update, BuiltQuery, Delete, Insert, Order, Select, Update,
+};
+pub use named::{
+    execute_named, query_named, query_one_named, query_scalar_named, split_statements, NamedParams,
+};
+pub use pages::{query_pages, Pages};
+#[doc(hidden)]
+pub use row::json_column;
+pub use row::{query_as, DbRow, FromColumn, Row};
+pub use table::DbTable;
+pub use transaction::{in_transaction, transaction, Tx};
+pub use value::{Hex, Json, ToDbValue, TryToDbValue};
+
+/// `#[derive(DbRow)]` / `#[derive(DbTable)]`（需启用 `derive` 特性）
+///
+/// ```rust
+/// use vtx_sdk::db::{DbRow, DbTable};
+/// use vtx_sdk::prelude::*;
+///
+/// #[derive(DbRow, DbTable)]
+/// #[db(table = "videos")]
+/// struct Video {
+///     id: String,
+///     title: String,
+///     #[db(rename = "is_public")]
+///     public: bool,
+///     #[db(json, default)]
+///     tags: Vec<String>,
+/// }
+///
+/// fn publish(id: &str) -> VtxResult<()> {
+///     let mut video = Video::find_by_id(id)?
+///         .ok_or_else(|| VtxError::NotFound(format!("video {}", id)))?;
+///     video.public = true;
+///     video.update_by_id()?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use vtx_sdk_derive::{DbRow, DbTable};
+
+pub use crate::error::db::{DbError, DbErrorKind};
+
+/// WIT 定义的数据库参数值
+pub use crate::imports::sql::DbValue;
+
+/// 映射 SQL 接口的宿主错误：优先归类为 `VtxError::Db`，其次按通用宿主错误分类，
+/// 均无法分类时视为 `DatabaseError`
+fn map_host_error(message: String) -> VtxError {
+    let host = HostError::parse(&message);
+    match DbError::from_host(&host) {
+        Some(err) => VtxError::Db(err),
+        None => host.into_vtx_error(VtxError::DatabaseError),
+    }
+}
+
+/// 查询结果无法转换为目标类型（列缺失、类型不匹配）
+///
+/// 这是插件的表结构与类型定义不一致，属于服务端错误（`Internal`，500），
+/// 不使用表示请求数据格式错误的 `SerializationError`（400）。
+pub(crate) fn decode_error(message: &dyn std::fmt::Display) -> VtxError {
+    VtxError::Internal(format!("Failed to decode query result: {}", message))
+}
+
+/// 执行非查询类 SQL（INSERT / UPDATE / DELETE）
+///
+/// # Parameters
+/// - `sql`: SQL 原始字符串，支持 `?` 占位符
+/// - `params`: 参数数组，元素需实现 `TryToDbValue`
+///
+/// # Returns
+/// - 成功：返回影响行数
+/// - 失败：约束冲突、锁定、语法错误等映射为 `VtxError::Db`（见 `error::db`），
+///   权限等通用宿主错误映射为对应变体，其余为 `VtxError::DatabaseError`；
+///   参数无法精确转换（如 `u64` 溢出）时为 `Internal`
+///
+/// ⚠️ 注意：Restricted 安全策略下禁止调用该接口
+pub fn execute(sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<u64> {
+    let wit_params = value::to_db_values(params)?;
+
+    sql::execute(sql, &wit_params).map_err(map_host_error)
+}
+
+/// 执行查询类 SQL（SELECT）并反序列化为目标类型列表
+///
+/// # Parameters
+/// - `sql`: SQL 字符串（支持 ? 占位符）
+/// - `params`: 参数数组（实现 `TryToDbValue`）
+///
+/// # Returns
+/// - 成功：反序列化后的结果集合
+/// - 失败：`DatabaseError`；结果无法反序列化为 `T` 时为 `Internal`（见 `decode_error`）
+///
+/// # Notes
+/// - 宿主接口返回的是 JSON 字符串
+/// - 为保证性能，建议单次返回控制在 1MB 内（大结果集使用 `query_pages` 自动分页）
+/// - 泛型 `T` 必须实现 `DeserializeOwned`（无需生命周期）
+///
+pub fn query<T: DeserializeOwned>(sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<Vec<T>> {
+    let wit_params = value::to_db_values(params)?;
+
+    let json_str = sql::query_json(sql, &wit_params).map_err(map_host_error)?;
+
+    serde_json::from_str(&json_str).map_err(|e| decode_error(&e))
+}
+
+/// 查询单行；结果为空时返回 `NotFound`，多于一行时返回 `DatabaseError`
+///
+/// `sql` 作为子查询包装并追加 `LIMIT 2`，宿主最多返回两行，不会读取整个结果集；
+/// 因此 `sql` 必须是 `SELECT`（或 `WITH` / `VALUES`），不能是带 `RETURNING` 的写语句。
+pub fn query_one<T: DeserializeOwned>(sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<T> {
+    query_optional(sql, params)?
+        .ok_or_else(|| VtxError::NotFound("query returned no rows".to_string()))
+}
+
+/// 查询单行；结果为空时返回 `None`，多于一行时返回 `DatabaseError`（限制同 `query_one`）
+pub fn query_optional<T: DeserializeOwned>(
+    sql: &str,
+    params: &[&dyn TryToDbValue],
+) -> VtxResult<Option<T>> {
+    let limited = format!(
+        "SELECT * FROM ({}) LIMIT 2",
+        sql.trim().trim_end_matches(';')
+    );
+    let mut rows: Vec<T> = query(&limited, params)?;
+    if rows.len() > 1 {
+        return Err(VtxError::DatabaseError(
+            "query returned more than one row".to_string(),
+        ));
+    }
+    Ok(rows.pop())
+}
+
+/// 查询单个值（唯一一行的唯一一列），按 `FromColumn` 转换
+///
+/// 结果为空时返回 `NotFound`；结果多于一行或列数不为 1 时返回 `DatabaseError`（见 `query_one`）。
+/// 值可能为 NULL 时使用 `Option<T>`（如 `SELECT MAX(views) FROM videos`）。
+///
+/// ```rust,no_run
+/// use vtx_sdk::db;
+///
+/// let total: i64 = db::query_scalar("SELECT COUNT(*) FROM videos WHERE owner_id = ?", &[&"u1"])?;
+/// # Ok::<(), vtx_sdk::error::VtxError>(())
+/// ```
+pub fn query_scalar<T: FromColumn>(sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<T> {
+    let row: Map<String, Value> = query_one(sql, params)?;
+    if row.len() != 1 {
+        return Err(VtxError::DatabaseError(format!(
+            "query_scalar expects exactly one column, got {}",
+            row.len()
+        )));
+    }
+    let (column, value) = row.into_iter().next().unwrap_or_default();
+    T::from_column(&value).map_err(|e| decode_error(&format_args!("column '{}': {}", column, e)))
+}
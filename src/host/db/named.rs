@@ -0,0 +1,308 @@
+//! Named SQL parameters (`:name` / `@name`) rewritten to positional `?`.
+//!
+//! 宿主接口只接受按顺序绑定的 `?` 参数。`execute_named` / `query_named` 在 SDK 侧扫描 SQL，
+//! 将命名占位符替换为 `?` 并按出现顺序展开参数（同名占位符可重复出现）。
+//! 字符串字面量、带引号的标识符与注释中的内容不会被识别为占位符，`::` 类型转换原样保留。
+//!
+//! ```rust,no_run
+//! use vtx_sdk::db;
+//!
+//! db::execute_named(
+//!     "UPDATE videos SET title = :title WHERE id = :id AND owner_id = @owner",
+//!     &db::params! { "title" => "renamed", "id" => 7, "owner" => "u1" },
+//! )?;
+//! # Ok::<(), vtx_sdk::error::VtxError>(())
+//! ```
+
+use super::{DbValue, FromColumn, TryToDbValue};
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+
+/// 构造命名参数列表：`params! { "name" => value, ... }`
+///
+/// 名称可带或不带 `:` / `@` 前缀；值需实现 `TryToDbValue`。
+#[macro_export]
+macro_rules! params {
+    () => {
+        $crate::db::NamedParams::new()
+    };
+    ($($name:expr => $value:expr),+ $(,)?) => {
+        $crate::db::NamedParams::new()$(.bind($name, $value))+
+    };
+}
+
+/// 命名参数列表（通常由 `params!` 构造）
+#[derive(Debug, Clone, Default)]
+pub struct NamedParams {
+    values: Vec<(String, DbValue)>,
+    error: Option<VtxError>,
+}
+
+impl NamedParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 绑定参数；重复绑定同名参数时以最后一次为准
+    ///
+    /// 值转换失败（如 `u64` 溢出）时记录错误，在执行时返回。
+    pub fn bind(mut self, name: &str, value: impl TryToDbValue) -> Self {
+        let name = name.trim_start_matches([':', '@']).to_string();
+        let value = match value.try_to_db_value() {
+            Ok(value) => value,
+            Err(err) => {
+                self.error.get_or_insert(err);
+                DbValue::NullVal
+            }
+        };
+        match self.values.iter_mut().find(|(n, _)| *n == name) {
+            Some(slot) => slot.1 = value,
+            None => self.values.push((name, value)),
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn position(&self, prefix: char, name: &str) -> VtxResult<usize> {
+        self.values
+            .iter()
+            .position(|(n, _)| n == name)
+            .ok_or_else(|| {
+                VtxError::Internal(format!("SQL parameter '{}{}' is not bound", prefix, name))
+            })
+    }
+
+    /// 将命名占位符改写为 `?`，返回改写后的 SQL 与按顺序排列的参数
+    ///
+    /// 以下情况返回 `Internal`：SQL 引用了未绑定的名称、绑定的名称未被使用、
+    /// 或命名占位符与位置占位符 `?` 混用。
+    ///
+    /// ```rust
+    /// use vtx_sdk::db;
+    ///
+    /// let (sql, params) = db::params! { "id" => 7 }
+    ///     .rewrite("SELECT * FROM v WHERE id = :id OR parent_id = :id AND note <> ':id'")
+    ///     .unwrap();
+    /// assert_eq!(sql, "SELECT * FROM v WHERE id = ? OR parent_id = ? AND note <> ':id'");
+    /// assert_eq!(params.len(), 2);
+    /// ```
+    pub fn rewrite(&self, sql: &str) -> VtxResult<(String, Vec<DbValue>)> {
+        if let Some(err) = &self.error {
+            return Err(err.clone());
+        }
+
+        let mut out = String::with_capacity(sql.len());
+        let mut params = Vec::new();
+        let mut used = vec![false; self.values.len()];
+        let mut positional = false;
+
+        let mut rest = sql;
+        while let Some(c) = rest.chars().next() {
+            let skip = match c {
+                '\'' | '"' | '`' => quoted_len(rest, c),
+                '[' => rest.find(']').map_or(rest.len(), |end| end + 1),
+                '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+                '/' if rest.starts_with("/*") => rest.find("*/").map_or(rest.len(), |end| end + 2),
+                '?' => {
+                    positional = true;
+                    1
+                }
+                // `::` 类型转换（如 `:id::text`）原样保留，不把其后的类型名视为参数
+                ':' if rest.starts_with("::") => rest.len() - rest.trim_start_matches(':').len(),
+                ':' | '@' => {
+                    let name_len = rest[1..]
+                        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                        .unwrap_or(rest.len() - 1);
+                    if name_len > 0 {
+                        let name = &rest[1..1 + name_len];
+                        let index = self.position(c, name)?;
+                        used[index] = true;
+                        params.push(self.values[index].1.clone());
+                        out.push('?');
+                        rest = &rest[1 + name_len..];
+                        continue;
+                    }
+                    1
+                }
+                _ => c.len_utf8(),
+            };
+            out.push_str(&rest[..skip]);
+            rest = &rest[skip..];
+        }
+
+        if positional && !params.is_empty() {
+            return Err(VtxError::Internal(
+                "named and positional (?) SQL parameters cannot be mixed".to_string(),
+            ));
+        }
+        if let Some(index) = used.iter().position(|u| !u) {
+            return Err(VtxError::Internal(format!(
+                "SQL parameter '{}' is bound but not used",
+                self.values[index].0
+            )));
+        }
+
+        Ok((out, params))
+    }
+}
+
+/// 将 SQL 脚本拆分为单条语句（宿主 `sql::execute` 每次只执行一条语句）
+///
+/// 按 `;` 切分，字符串字面量、带引号的标识符与注释中的 `;` 不参与切分；
+/// `CREATE TRIGGER ... BEGIN ... END` 按 `BEGIN` / `CASE` 与 `END` 配对保持为一条语句。
+/// 返回的语句不含结尾的 `;`，只包含空白或注释的片段会被丢弃。
+///
+/// ```rust
+/// use vtx_sdk::db;
+///
+/// let statements = db::split_statements(
+///     "DROP INDEX idx_title; -- ; in a comment\n\
+///      CREATE TRIGGER t AFTER DELETE ON a BEGIN DELETE FROM b WHERE note = ';'; END;\n\
+///      DROP TABLE a;",
+/// );
+/// assert_eq!(statements.len(), 3);
+/// assert_eq!(statements[0], "DROP INDEX idx_title");
+/// assert!(statements[1].ends_with("END"));
+/// ```
+pub fn split_statements(sql: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    // 当前语句首个非注释字符的位置
+    let mut start: Option<usize> = None;
+    // 当前语句的前三个关键字（判断 CREATE [TEMP] TRIGGER）与 BEGIN/CASE-END 嵌套深度
+    let mut words: Vec<String> = Vec::new();
+    let mut depth = 0i32;
+
+    let mut pos = 0;
+    while let Some(c) = sql[pos..].chars().next() {
+        let rest = &sql[pos..];
+        let skip = match c {
+            '-' if rest.starts_with("--") => {
+                pos += rest.find('\n').unwrap_or(rest.len());
+                continue;
+            }
+            '/' if rest.starts_with("/*") => {
+                pos += rest.find("*/").map_or(rest.len(), |end| end + 2);
+                continue;
+            }
+            ';' => {
+                if let Some(begin) = start {
+                    if !(is_trigger(&words) && depth > 0) {
+                        out.push(sql[begin..pos].trim_end());
+                        start = None;
+                        words.clear();
+                        depth = 0;
+                    }
+                }
+                pos += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                pos += c.len_utf8();
+                continue;
+            }
+            '\'' | '"' | '`' => quoted_len(rest, c),
+            '[' => rest.find(']').map_or(rest.len(), |end| end + 1),
+            c if c.is_alphabetic() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .unwrap_or(rest.len());
+                let word = rest[..len].to_ascii_uppercase();
+                match word.as_str() {
+                    "BEGIN" | "CASE" => depth += 1,
+                    "END" => depth -= 1,
+                    _ => {}
+                }
+                if words.len() < 3 {
+                    words.push(word);
+                }
+                len
+            }
+            _ => c.len_utf8(),
+        };
+        start.get_or_insert(pos);
+        pos += skip;
+    }
+
+    if let Some(begin) = start {
+        out.push(sql[begin..].trim_end());
+    }
+    out
+}
+
+/// 统计位置占位符 `?` 的数量（字符串字面量、带引号的标识符与注释中的 `?` 不计入）
+pub(super) fn count_placeholders(sql: &str) -> usize {
+    let mut count = 0;
+    let mut rest = sql;
+    while let Some(c) = rest.chars().next() {
+        let skip = match c {
+            '\'' | '"' | '`' => quoted_len(rest, c),
+            '[' => rest.find(']').map_or(rest.len(), |end| end + 1),
+            '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+            '/' if rest.starts_with("/*") => rest.find("*/").map_or(rest.len(), |end| end + 2),
+            '?' => {
+                count += 1;
+                1
+            }
+            _ => c.len_utf8(),
+        };
+        rest = &rest[skip..];
+    }
+    count
+}
+
+/// 语句是否以 `CREATE [TEMP | TEMPORARY] TRIGGER` 开头
+fn is_trigger(words: &[String]) -> bool {
+    match words {
+        [create, trigger, ..] if create == "CREATE" && trigger == "TRIGGER" => true,
+        [create, temp, trigger] => {
+            create == "CREATE" && (temp == "TEMP" || temp == "TEMPORARY") && trigger == "TRIGGER"
+        }
+        _ => false,
+    }
+}
+
+/// 引号包裹内容的长度（含首尾引号，连续两个引号视为转义）
+fn quoted_len(text: &str, quote: char) -> usize {
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            if matches!(chars.peek(), Some((_, next)) if *next == quote) {
+                chars.next();
+                continue;
+            }
+            return i + c.len_utf8();
+        }
+    }
+    text.len()
+}
+
+fn positional_refs(params: &[DbValue]) -> Vec<&dyn TryToDbValue> {
+    params.iter().map(|p| p as &dyn TryToDbValue).collect()
+}
+
+/// 使用命名参数执行非查询类 SQL（同 `db::execute`）
+pub fn execute_named(sql: &str, params: &NamedParams) -> VtxResult<u64> {
+    let (sql, values) = params.rewrite(sql)?;
+    super::execute(&sql, &positional_refs(&values))
+}
+
+/// 使用命名参数执行查询（同 `db::query`）
+pub fn query_named<T: DeserializeOwned>(sql: &str, params: &NamedParams) -> VtxResult<Vec<T>> {
+    let (sql, values) = params.rewrite(sql)?;
+    super::query(&sql, &positional_refs(&values))
+}
+
+/// 使用命名参数查询单行（同 `db::query_one`）
+pub fn query_one_named<T: DeserializeOwned>(sql: &str, params: &NamedParams) -> VtxResult<T> {
+    let (sql, values) = params.rewrite(sql)?;
+    super::query_one(&sql, &positional_refs(&values))
+}
+
+/// 使用命名参数查询单个值（同 `db::query_scalar`）
+pub fn query_scalar_named<T: FromColumn>(sql: &str, params: &NamedParams) -> VtxResult<T> {
+    let (sql, values) = params.rewrite(sql)?;
+    super::query_scalar(&sql, &positional_refs(&values))
+}
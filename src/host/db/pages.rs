@@ -0,0 +1,191 @@
+//! Automatic pagination for large result sets (`query_pages`).
+
+use super::builder::quote_ident;
+use super::value::to_db_values;
+use super::{DbValue, Order, TryToDbValue};
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::marker::PhantomData;
+
+/// 分页方式
+#[derive(Debug, Clone)]
+enum Mode {
+    /// `LIMIT n OFFSET m`
+    Offset { offset: u64 },
+    /// `WHERE key > last ORDER BY key LIMIT n`
+    Keyset {
+        column: String,
+        order: Order,
+        last: Option<DbValue>,
+    },
+}
+
+/// 分页查询迭代器（由 `db::query_pages` 创建）
+///
+/// 每次迭代执行一次查询并返回一页结果；结果不足一页或为空时结束，出错后不再继续。
+pub struct Pages<T> {
+    sql: String,
+    params: VtxResult<Vec<DbValue>>,
+    page_size: u64,
+    mode: Mode,
+    done: bool,
+    _row: PhantomData<fn() -> T>,
+}
+
+/// 分页执行查询，避免单次返回过大的结果集
+///
+/// `sql` 作为子查询包装，不应自带 `LIMIT` / `OFFSET`。默认使用偏移分页，
+/// 此时 `sql` 需包含能确定唯一顺序的 `ORDER BY`；数据量大或并发写入时建议使用
+/// `keyset` 按唯一列分页（结果集中必须包含该列）。
+///
+/// ```rust,no_run
+/// use vtx_sdk::db::{self, Order};
+/// use vtx_sdk::prelude::*;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Video {
+///     id: i64,
+///     title: String,
+/// }
+///
+/// fn reindex() -> VtxResult<()> {
+///     let pages = db::query_pages::<Video>("SELECT id, title FROM videos", &[], 500)
+///         .keyset("id", Order::Asc);
+///     for page in pages {
+///         for video in page? {
+///             let _ = (video.id, video.title);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn query_pages<T: DeserializeOwned>(
+    sql: &str,
+    params: &[&dyn TryToDbValue],
+    page_size: u64,
+) -> Pages<T> {
+    Pages {
+        sql: sql.trim().trim_end_matches(';').to_string(),
+        params: to_db_values(params),
+        page_size: page_size.max(1),
+        mode: Mode::Offset { offset: 0 },
+        done: false,
+        _row: PhantomData,
+    }
+}
+
+impl<T: DeserializeOwned> Pages<T> {
+    /// 改用键集分页：按 `column` 排序，下一页从上一页最后一行的键值之后开始
+    pub fn keyset(mut self, column: &str, order: Order) -> Self {
+        self.mode = Mode::Keyset {
+            column: column.to_string(),
+            order,
+            last: None,
+        };
+        self
+    }
+
+    /// 逐行迭代（按需加载下一页）
+    pub fn rows(self) -> impl Iterator<Item = VtxResult<T>> {
+        self.flat_map(|page| {
+            let rows: Vec<VtxResult<T>> = match page {
+                Ok(rows) => rows.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            };
+            rows
+        })
+    }
+
+    fn fetch_page(&mut self) -> VtxResult<Vec<T>> {
+        let mut params = self.params.clone()?;
+        let sql = match &self.mode {
+            Mode::Offset { offset } => format!(
+                "SELECT * FROM ({}) LIMIT {} OFFSET {}",
+                self.sql, self.page_size, offset
+            ),
+            Mode::Keyset {
+                column,
+                order,
+                last,
+            } => {
+                let (op, dir) = match order {
+                    Order::Asc => (">", "ASC"),
+                    Order::Desc => ("<", "DESC"),
+                };
+                let column = quote_ident(column);
+                let filter = match last {
+                    Some(last) => {
+                        params.push(last.clone());
+                        format!(" WHERE {} {} ?", column, op)
+                    }
+                    None => String::new(),
+                };
+                format!(
+                    "SELECT * FROM ({}){} ORDER BY {} {} LIMIT {}",
+                    self.sql, filter, column, dir, self.page_size
+                )
+            }
+        };
+
+        let refs: Vec<&dyn TryToDbValue> = params.iter().map(|p| p as &dyn TryToDbValue).collect();
+        let rows: Vec<Map<String, Value>> = super::query(&sql, &refs)?;
+        if (rows.len() as u64) < self.page_size {
+            self.done = true;
+        }
+
+        match &mut self.mode {
+            Mode::Offset { offset } => *offset += rows.len() as u64,
+            Mode::Keyset { column, last, .. } => {
+                if let Some(row) = rows.last() {
+                    *last = Some(key_value(column, row)?);
+                }
+            }
+        }
+
+        rows.into_iter()
+            .map(|row| {
+                serde_json::from_value(Value::Object(row)).map_err(|e| super::decode_error(&e))
+            })
+            .collect()
+    }
+}
+
+/// 从结果行读取键集分页的键值
+fn key_value(column: &str, row: &Map<String, Value>) -> VtxResult<DbValue> {
+    let missing = || {
+        VtxError::Internal(format!(
+            "keyset column '{}' must be a non-null column of the result set",
+            column
+        ))
+    };
+    match row.get(column).ok_or_else(missing)? {
+        Value::String(s) => Ok(DbValue::Text(s.clone())),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(DbValue::Integer(i)),
+            None => n.as_f64().map(DbValue::Real).ok_or_else(missing),
+        },
+        _ => Err(missing()),
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Pages<T> {
+    type Item = VtxResult<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.fetch_page() {
+            Ok(rows) if rows.is_empty() => {
+                self.done = true;
+                None
+            }
+            Ok(rows) => Some(Ok(rows)),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
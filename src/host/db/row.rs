@@ -0,0 +1,234 @@
+//! Typed row mapping for query results (`DbRow`).
+
+use super::{DbValue, TryToDbValue};
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// 查询结果中的一行（列名 → 宿主返回的 JSON 值）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Row {
+    columns: Map<String, Value>,
+}
+
+impl From<Map<String, Value>> for Row {
+    fn from(columns: Map<String, Value>) -> Self {
+        Self { columns }
+    }
+}
+
+impl Row {
+    /// 原始列值（列不存在时为 `None`）
+    pub fn raw(&self, column: &str) -> Option<&Value> {
+        self.columns.get(column)
+    }
+
+    /// 读取列并转换为目标类型；列缺失视为 NULL
+    pub fn get<T: FromColumn>(&self, column: &str) -> VtxResult<T> {
+        let value = self.columns.get(column).unwrap_or(&Value::Null);
+        T::from_column(value)
+            .map_err(|e| column_error(column, e, self.columns.contains_key(column)))
+    }
+
+    /// 读取列；缺失或为 NULL 时返回 `Default::default()`
+    pub fn get_or_default<T: FromColumn + Default>(&self, column: &str) -> VtxResult<T> {
+        match self.columns.get(column) {
+            None | Some(Value::Null) => Ok(T::default()),
+            Some(_) => self.get(column),
+        }
+    }
+
+    /// 读取 JSON 列（文本内容按 JSON 解析，其余值直接反序列化）
+    pub fn get_json<T: DeserializeOwned>(&self, column: &str) -> VtxResult<T> {
+        let value = self.columns.get(column).unwrap_or(&Value::Null);
+        let result = match value {
+            Value::String(text) => serde_json::from_str(text),
+            other => serde_json::from_value(other.clone()),
+        };
+        result.map_err(|e| column_error(column, e.to_string(), self.columns.contains_key(column)))
+    }
+
+    /// 读取 JSON 列；缺失或为 NULL 时返回 `Default::default()`
+    pub fn get_json_or_default<T: DeserializeOwned + Default>(&self, column: &str) -> VtxResult<T> {
+        match self.columns.get(column) {
+            None | Some(Value::Null) => Ok(T::default()),
+            Some(_) => self.get_json(column),
+        }
+    }
+
+    /// 列名列表
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.columns.keys().map(String::as_str)
+    }
+}
+
+fn column_error(column: &str, message: String, present: bool) -> VtxError {
+    if present {
+        super::decode_error(&format_args!("column '{}': {}", column, message))
+    } else {
+        super::decode_error(&format_args!(
+            "column '{}' not found in row: {}",
+            column, message
+        ))
+    }
+}
+
+/// 从单个列值转换（宿主以 JSON 编码列值）
+pub trait FromColumn: Sized {
+    fn from_column(value: &Value) -> Result<Self, String>;
+}
+
+fn type_error(expected: &str, value: &Value) -> String {
+    format!("expected {}, found {}", expected, value)
+}
+
+impl FromColumn for i64 {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Number(n) => n.as_i64().ok_or_else(|| type_error("integer", value)),
+            Value::Bool(b) => Ok(i64::from(*b)),
+            _ => Err(type_error("integer", value)),
+        }
+    }
+}
+
+/// 其余整数类型经 `i64` 转换并做范围检查
+macro_rules! from_column_int {
+    ($($ty:ty),*) => {$(
+        impl FromColumn for $ty {
+            fn from_column(value: &Value) -> Result<Self, String> {
+                let n = i64::from_column(value)?;
+                <$ty>::try_from(n).map_err(|_| {
+                    format!("integer {} out of range for {}", n, stringify!($ty))
+                })
+            }
+        }
+    )*};
+}
+
+from_column_int!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+impl FromColumn for f64 {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        value.as_f64().ok_or_else(|| type_error("number", value))
+    }
+}
+
+impl FromColumn for f32 {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        f64::from_column(value).map(|v| v as f32)
+    }
+}
+
+/// 接受 JSON 布尔值、`0/1` 整数（`ToDbValue for bool` 的存储形式）与 `"true"/"false"` 文本
+impl FromColumn for bool {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Bool(b) => Ok(*b),
+            Value::Number(n) => match n.as_i64() {
+                Some(0) => Ok(false),
+                Some(1) => Ok(true),
+                _ => Err(type_error("0 or 1", value)),
+            },
+            Value::String(s) => match s.as_str() {
+                "0" | "false" => Ok(false),
+                "1" | "true" => Ok(true),
+                _ => Err(type_error("boolean", value)),
+            },
+            _ => Err(type_error("boolean", value)),
+        }
+    }
+}
+
+impl FromColumn for String {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::String(s) => Ok(s.clone()),
+            Value::Number(n) => Ok(n.to_string()),
+            _ => Err(type_error("text", value)),
+        }
+    }
+}
+
+/// BLOB 列（宿主编码为字节数组）
+///
+/// 文本列按原样取 UTF-8 字节，不会猜测其编码；以十六进制文本存储的字节串请读取为 `db::Hex`。
+impl FromColumn for Vec<u8> {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .map(|v| {
+                    v.as_u64()
+                        .and_then(|b| u8::try_from(b).ok())
+                        .ok_or_else(|| type_error("byte", v))
+                })
+                .collect(),
+            Value::String(s) => Ok(s.as_bytes().to_vec()),
+            _ => Err(type_error("blob", value)),
+        }
+    }
+}
+
+/// 解码十六进制文本（大小写不敏感）；长度为奇数或含非十六进制字符时返回 `None`
+pub(super) fn hex_decode(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+impl FromColumn for Value {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        Ok(value.clone())
+    }
+}
+
+impl<T: FromColumn> FromColumn for Option<T> {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Null => Ok(None),
+            other => T::from_column(other).map(Some),
+        }
+    }
+}
+
+/// 可由查询结果行构造的类型
+///
+/// 通常通过 `#[derive(DbRow)]`（`derive` 特性）生成，也可手动实现。
+pub trait DbRow: Sized {
+    fn from_row(row: &Row) -> VtxResult<Self>;
+}
+
+impl DbRow for Row {
+    fn from_row(row: &Row) -> VtxResult<Self> {
+        Ok(row.clone())
+    }
+}
+
+/// 执行查询并按 `DbRow` 映射每一行
+///
+/// 与 `db::query` 不同，列值先经过 `FromColumn` 转换，
+/// 因此 `0/1` 可读取为 `bool`，JSON 文本列可读取为结构体。
+pub fn query_as<T: DbRow>(sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<Vec<T>> {
+    let rows: Vec<Map<String, Value>> = super::query(sql, params)?;
+    rows.into_iter()
+        .map(|r| T::from_row(&Row::from(r)))
+        .collect()
+}
+
+/// 将字段序列化为 JSON 文本列（供 `#[db(json)]` 使用，`None` 存为 NULL）
+#[doc(hidden)]
+pub fn json_column<T: serde::Serialize>(value: &T) -> VtxResult<DbValue> {
+    match serde_json::to_value(value) {
+        Ok(Value::Null) => Ok(DbValue::NullVal),
+        Ok(json) => Ok(DbValue::Text(json.to_string())),
+        Err(e) => Err(VtxError::Internal(format!(
+            "Failed to serialize column value: {}",
+            e
+        ))),
+    }
+}
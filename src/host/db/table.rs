@@ -0,0 +1,81 @@
+//! Single-table CRUD on top of `DbRow` and the query builder (`DbTable`).
+
+use super::{DbRow, DbValue, TryToDbValue};
+use crate::error::VtxResult;
+
+/// 映射到单张表的行类型
+///
+/// 通常通过 `#[derive(DbRow, DbTable)]`（`derive` 特性）生成元数据，
+/// CRUD 方法由本 trait 基于 `db::insert` / `update` / `select` / `delete` 提供默认实现。
+///
+/// 派生示例见 `db::DbTable` 派生宏（`derive` 特性）。
+pub trait DbTable: DbRow {
+    /// 表名
+    const TABLE: &'static str;
+    /// 主键列名
+    const PRIMARY_KEY: &'static str;
+
+    /// 全部持久化列（与 `values` 顺序一致）
+    fn columns() -> &'static [&'static str];
+
+    /// 当前行的列值（与 `columns` 顺序一致）
+    fn values(&self) -> VtxResult<Vec<DbValue>>;
+
+    /// 当前行的主键值
+    fn primary_key_value(&self) -> VtxResult<DbValue>;
+
+    /// 插入当前行，返回影响行数
+    fn insert(&self) -> VtxResult<u64> {
+        Self::columns()
+            .iter()
+            .zip(self.values()?)
+            .fold(super::insert(Self::TABLE), |q, (col, value)| {
+                q.value(col, value)
+            })
+            .execute()
+    }
+
+    /// 按主键更新其余全部列，返回影响行数（0 表示记录不存在）
+    ///
+    /// 表只有主键列时没有可更新的列，返回 `Internal` 错误。
+    fn update_by_id(&self) -> VtxResult<u64> {
+        let update = Self::columns()
+            .iter()
+            .zip(self.values()?)
+            .filter(|(col, _)| **col != Self::PRIMARY_KEY)
+            .fold(super::update(Self::TABLE), |q, (col, value)| {
+                q.set(col, value)
+            });
+
+        update
+            .where_eq(Self::PRIMARY_KEY, self.primary_key_value()?)
+            .execute()
+    }
+
+    /// 按主键查询
+    fn find_by_id(id: impl TryToDbValue) -> VtxResult<Option<Self>> {
+        let rows = super::select(Self::TABLE)
+            .columns(Self::columns())
+            .where_eq(Self::PRIMARY_KEY, id)
+            .limit(1)
+            .build()
+            .fetch_as()?;
+
+        Ok(rows.into_iter().next())
+    }
+
+    /// 按主键删除，返回影响行数
+    fn delete_by_id(id: impl TryToDbValue) -> VtxResult<u64> {
+        super::delete(Self::TABLE)
+            .where_eq(Self::PRIMARY_KEY, id)
+            .execute()
+    }
+
+    /// 查询全部行
+    fn find_all() -> VtxResult<Vec<Self>> {
+        super::select(Self::TABLE)
+            .columns(Self::columns())
+            .build()
+            .fetch_as()
+    }
+}
@@ -0,0 +1,195 @@
+//! Transactions and nested savepoints on top of `sql::execute`.
+
+use super::{FromColumn, NamedParams, TryToDbValue};
+use crate::error::VtxResult;
+use serde::de::DeserializeOwned;
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+thread_local! {
+    // 当前线程已开启的事务层数（0 表示不在事务中）
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+/// 事务句柄
+///
+/// 由 `db::transaction` 创建，仅在闭包内有效。
+/// 提供与 `db` 模块相同的 `execute` / `query` 接口，并可通过 `transaction` 开启嵌套保存点。
+pub struct Tx {
+    depth: u32,
+    // 事务状态按线程记录，句柄不应跨线程传递
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Tx {
+    /// 事务层级（1 为最外层事务，≥2 为保存点）
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// 在事务内执行非查询类 SQL（同 `db::execute`）
+    pub fn execute(&self, sql: &str, params: &[&dyn TryToDbValue]) -> VtxResult<u64> {
+        super::execute(sql, params)
+    }
+
+    /// 在事务内执行查询（同 `db::query`）
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &[&dyn TryToDbValue],
+    ) -> VtxResult<Vec<T>> {
+        super::query(sql, params)
+    }
+
+    /// 在事务内查询单行（同 `db::query_one`）
+    pub fn query_one<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &[&dyn TryToDbValue],
+    ) -> VtxResult<T> {
+        super::query_one(sql, params)
+    }
+
+    /// 在事务内查询单行（同 `db::query_optional`）
+    pub fn query_optional<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &[&dyn TryToDbValue],
+    ) -> VtxResult<Option<T>> {
+        super::query_optional(sql, params)
+    }
+
+    /// 在事务内查询单个值（同 `db::query_scalar`）
+    pub fn query_scalar<T: FromColumn>(
+        &self,
+        sql: &str,
+        params: &[&dyn TryToDbValue],
+    ) -> VtxResult<T> {
+        super::query_scalar(sql, params)
+    }
+
+    /// 在事务内使用命名参数执行（同 `db::execute_named`）
+    pub fn execute_named(&self, sql: &str, params: &NamedParams) -> VtxResult<u64> {
+        super::execute_named(sql, params)
+    }
+
+    /// 在事务内使用命名参数查询（同 `db::query_named`）
+    pub fn query_named<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &NamedParams,
+    ) -> VtxResult<Vec<T>> {
+        super::query_named(sql, params)
+    }
+
+    /// 开启嵌套保存点（同 `db::transaction`）
+    pub fn transaction<T>(&self, f: impl FnOnce(&Tx) -> VtxResult<T>) -> VtxResult<T> {
+        transaction(f)
+    }
+}
+
+/// 事务作用域守卫：未显式完成时（`Err` 或 panic 展开）自动回滚
+struct Scope {
+    depth: u32,
+    finished: bool,
+}
+
+impl Scope {
+    fn begin() -> VtxResult<Self> {
+        let depth = DEPTH.with(Cell::get) + 1;
+        let sql = if depth == 1 {
+            "BEGIN".to_string()
+        } else {
+            format!("SAVEPOINT {}", savepoint_name(depth))
+        };
+
+        super::execute(&sql, &[])?;
+        DEPTH.with(|d| d.set(depth));
+
+        Ok(Self {
+            depth,
+            finished: false,
+        })
+    }
+
+    fn commit(mut self) -> VtxResult<()> {
+        self.finished = true;
+        DEPTH.with(|d| d.set(self.depth - 1));
+
+        let sql = if self.depth == 1 {
+            "COMMIT".to_string()
+        } else {
+            format!("RELEASE SAVEPOINT {}", savepoint_name(self.depth))
+        };
+
+        super::execute(&sql, &[]).inspect_err(|_| rollback(self.depth))?;
+        Ok(())
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if !self.finished {
+            DEPTH.with(|d| d.set(self.depth - 1));
+            rollback(self.depth);
+        }
+    }
+}
+
+/// 回滚指定层级；回滚失败时保留原始错误，不再覆盖
+fn rollback(depth: u32) {
+    if depth == 1 {
+        let _ = super::execute("ROLLBACK", &[]);
+    } else {
+        let name = savepoint_name(depth);
+        let _ = super::execute(&format!("ROLLBACK TO SAVEPOINT {}", name), &[]);
+        let _ = super::execute(&format!("RELEASE SAVEPOINT {}", name), &[]);
+    }
+}
+
+fn savepoint_name(depth: u32) -> String {
+    format!("vtx_sp_{}", depth)
+}
+
+/// 在事务中执行闭包
+///
+/// - 闭包返回 `Ok`：提交（`COMMIT`）
+/// - 闭包返回 `Err` 或发生 panic：回滚（`ROLLBACK`），并原样返回错误
+/// - 在事务内再次调用（`db::transaction` 或 `Tx::transaction`）：使用 `SAVEPOINT`，
+///   内层回滚不影响外层已执行的语句
+///
+/// 基于 `BEGIN` / `SAVEPOINT` 语句实现，需要宿主对同一插件实例复用数据库连接。
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::prelude::*;
+///
+/// fn create_video(id: &str, title: &str, tags: &[&str]) -> VtxResult<()> {
+///     db::transaction(|tx| {
+///         tx.execute("INSERT INTO videos (id, title) VALUES (?, ?)", &[&id, &title])?;
+///         for tag in tags {
+///             tx.execute("INSERT INTO video_tags (video_id, tag) VALUES (?, ?)", &[&id, tag])?;
+///         }
+///         tx.execute("INSERT INTO audit_log (action) VALUES ('video.created')", &[])?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub fn transaction<T>(f: impl FnOnce(&Tx) -> VtxResult<T>) -> VtxResult<T> {
+    let scope = Scope::begin()?;
+    let tx = Tx {
+        depth: scope.depth,
+        _not_send: PhantomData,
+    };
+
+    // `Err` 时 `scope` 在此处析构并回滚
+    let value = f(&tx)?;
+    scope.commit()?;
+    Ok(value)
+}
+
+/// 当前线程是否处于事务中
+pub fn in_transaction() -> bool {
+    DEPTH.with(Cell::get) > 0
+}
@@ -0,0 +1,629 @@
+//! Rust → `DbValue` parameter conversions (`ToDbValue` / `TryToDbValue`).
+//!
+//! `DbValue` 只有 TEXT / INTEGER / REAL / NULL 四种形式，其余类型按以下约定编码：
+//!
+//! | Rust 类型 | 参数形式 |
+//! |---|---|
+//! | `bool` | INTEGER `0` / `1` |
+//! | `Vec<u8>` / `&[u8]` / `[u8; N]` / `db::Hex<T>` | TEXT，小写十六进制；写入 BLOB 列时使用 `unhex(?)`（SQLite 3.41+） |
+//! | `serde_json::Value` / `db::Json<T>` | TEXT，JSON 文本（JSON `null` 为 NULL） |
+//! | `chrono` / `time` 日期时间 | TEXT，定宽的 `YYYY-MM-DD HH:MM:SS.ffffff`（精确到微秒，带时区的时间先转换为 UTC）；日期为 `YYYY-MM-DD`，时间为 `HH:MM:SS.ffffff` |
+//! | `uuid::Uuid` | TEXT，小写连字符格式 |
+//!
+//! 日期时间文本与 SQLite `datetime('now', 'subsec')` 的形式一致，可直接用于 `datetime()` 等函数；
+//! 各部分均为定宽（年份限 0000–9999），因此同一列内的字典序与时间顺序一致。
+//!
+//! 无损转换实现 `ToDbValue`；可能失败的转换（`u64` / `usize` / `isize` 超出 INTEGER 范围、
+//! `Json<T>` 序列化失败、日期年份超出 0000–9999）只实现 `TryToDbValue`，不提供静默改写参数的
+//! 不可失败版本。SDK 的所有执行入口（`db::execute`、构造器、`DbTable` 等）都接受 `TryToDbValue`，
+//! 实现了 `ToDbValue` 的类型自动满足该约束。
+
+use crate::error::{VtxError, VtxResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+use super::row::{hex_decode, json_column, FromColumn};
+use super::DbValue;
+
+/// Trait：用于将 Rust 类型转换为 WIT 定义的 `DbValue`
+///
+/// 适用于数据库跨边界调用参数传递；只用于无损转换，可能失败的类型实现 `TryToDbValue`。
+pub trait ToDbValue {
+    fn to_db_value(&self) -> DbValue;
+}
+
+/// Trait：可失败的 `DbValue` 转换
+///
+/// 溢出、序列化失败等情况返回错误而不是静默改写参数；所有 `ToDbValue` 类型自动实现。
+///
+/// ```rust
+/// use vtx_sdk::db::TryToDbValue;
+///
+/// assert!(42u64.try_to_db_value().is_ok());
+/// assert!(u64::MAX.try_to_db_value().is_err());
+/// ```
+pub trait TryToDbValue {
+    fn try_to_db_value(&self) -> VtxResult<DbValue>;
+}
+
+impl<T: ToDbValue + ?Sized> TryToDbValue for T {
+    fn try_to_db_value(&self) -> VtxResult<DbValue> {
+        Ok(self.to_db_value())
+    }
+}
+
+/// 参数无法精确表示为 `DbValue`
+///
+/// 这是插件传入了数据库无法保存的值，属于服务端错误（`Internal`，500）。
+fn encode_error(message: impl fmt::Display) -> VtxError {
+    VtxError::Internal(format!("Failed to encode query parameter: {}", message))
+}
+
+/// 按 `try_to_db_value` 转换参数列表（遇到首个错误即返回）
+pub(super) fn to_db_values(params: &[&dyn TryToDbValue]) -> VtxResult<Vec<DbValue>> {
+    params.iter().map(|p| p.try_to_db_value()).collect()
+}
+
+// --- 基本类型到 DbValue 的映射实现 ---
+
+impl ToDbValue for String {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Text(self.clone())
+    }
+}
+
+impl ToDbValue for &str {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Text(self.to_string())
+    }
+}
+
+/// 可无损转换为 `i64` 的整数
+macro_rules! to_db_value_int {
+    ($($ty:ty),*) => {$(
+        impl ToDbValue for $ty {
+            fn to_db_value(&self) -> DbValue {
+                DbValue::Integer(i64::from(*self))
+            }
+        }
+    )*};
+}
+
+to_db_value_int!(i8, i16, i32, i64, u8, u16, u32);
+
+/// 可能超出 `i64` 范围的整数：只提供可失败的转换（含引用形式，便于直接传 `&id`）
+macro_rules! try_to_db_value_int {
+    ($($ty:ty),*) => {$(
+        impl TryToDbValue for $ty {
+            fn try_to_db_value(&self) -> VtxResult<DbValue> {
+                i64::try_from(*self).map(DbValue::Integer).map_err(|_| {
+                    encode_error(format_args!(
+                        "{} {} does not fit in a SQLite INTEGER",
+                        stringify!($ty),
+                        self
+                    ))
+                })
+            }
+        }
+
+        impl TryToDbValue for &$ty {
+            fn try_to_db_value(&self) -> VtxResult<DbValue> {
+                (**self).try_to_db_value()
+            }
+        }
+    )*};
+}
+
+try_to_db_value_int!(u64, usize, isize);
+
+impl ToDbValue for f64 {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Real(*self)
+    }
+}
+
+impl ToDbValue for f32 {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Real(*self as f64)
+    }
+}
+
+impl ToDbValue for bool {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Integer(if *self { 1 } else { 0 })
+    }
+}
+
+impl ToDbValue for () {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::NullVal
+    }
+}
+
+impl ToDbValue for DbValue {
+    fn to_db_value(&self) -> DbValue {
+        self.clone()
+    }
+}
+
+impl<T: ToDbValue + ?Sized> ToDbValue for &T {
+    fn to_db_value(&self) -> DbValue {
+        (**self).to_db_value()
+    }
+}
+
+/// `None` 为 NULL
+impl<T: TryToDbValue> TryToDbValue for Option<T> {
+    fn try_to_db_value(&self) -> VtxResult<DbValue> {
+        match self {
+            Some(v) => v.try_to_db_value(),
+            None => Ok(DbValue::NullVal),
+        }
+    }
+}
+
+// --- BLOB ---
+
+/// 字节串编码为小写十六进制文本
+pub(super) fn hex_encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// BLOB 参数（十六进制文本，写入时配合 `unhex(?)`）
+///
+/// 读取时 `Vec<u8>` 只接受真正的 BLOB 列（或按原样取文本的 UTF-8 字节）；
+/// 未经 `unhex(?)` 而以十六进制文本存储的列需读取为 `db::Hex`。
+///
+/// ```rust
+/// use vtx_sdk::db::{DbValue, ToDbValue};
+///
+/// let thumb: &[u8] = &[0xde, 0xad, 0xbe, 0xef];
+/// assert!(matches!(thumb.to_db_value(), DbValue::Text(ref s) if s == "deadbeef"));
+/// // db::execute("INSERT INTO thumbs (id, data) VALUES (?, unhex(?))", &[&id, &thumb])?;
+/// ```
+impl ToDbValue for [u8] {
+    fn to_db_value(&self) -> DbValue {
+        DbValue::Text(hex_encode(self))
+    }
+}
+
+impl ToDbValue for Vec<u8> {
+    fn to_db_value(&self) -> DbValue {
+        self.as_slice().to_db_value()
+    }
+}
+
+impl<const N: usize> ToDbValue for [u8; N] {
+    fn to_db_value(&self) -> DbValue {
+        self.as_slice().to_db_value()
+    }
+}
+
+/// 十六进制文本列包装：写入时编码为小写十六进制，读取时严格按十六进制解码
+///
+/// 用于直接把字节串存成 TEXT 的列（不经 `unhex(?)`）；读取到非十六进制文本时返回错误，
+/// 而不是像猜测编码那样把 `"cafe"` 之类的普通文本误解码为字节。
+///
+/// ```rust
+/// use serde_json::json;
+/// use vtx_sdk::db::{FromColumn, Hex};
+///
+/// assert_eq!(Hex::<Vec<u8>>::from_column(&json!("cafe")).unwrap().0, [0xca, 0xfe]);
+/// assert!(Hex::<Vec<u8>>::from_column(&json!("coffee")).is_err());
+/// // 不带 Hex 包装时文本按原样读取
+/// assert_eq!(Vec::<u8>::from_column(&json!("cafe")).unwrap(), b"cafe");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Hex<T>(pub T);
+
+impl<T> std::ops::Deref for Hex<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: AsRef<[u8]>> ToDbValue for Hex<T> {
+    fn to_db_value(&self) -> DbValue {
+        self.0.as_ref().to_db_value()
+    }
+}
+
+/// 文本按十六进制解码；BLOB 列（字节数组）原样接受
+impl<T: From<Vec<u8>>> FromColumn for Hex<T> {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        let bytes = match value {
+            Value::String(text) => hex_decode(text)
+                .ok_or_else(|| format!("expected hexadecimal text, found {}", value))?,
+            other => Vec::<u8>::from_column(other)?,
+        };
+        Ok(Hex(T::from(bytes)))
+    }
+}
+
+// --- JSON ---
+
+/// JSON 文本（JSON `null` 为 NULL）
+impl ToDbValue for Value {
+    fn to_db_value(&self) -> DbValue {
+        match self {
+            Value::Null => DbValue::NullVal,
+            other => DbValue::Text(other.to_string()),
+        }
+    }
+}
+
+/// JSON 列包装：写入时序列化为 JSON 文本，读取时从 JSON 文本反序列化
+///
+/// 与 `#[db(json)]` 字段属性的存储形式一致，可用于参数、`Row::get` 与手写的 `DbRow`。
+///
+/// ```rust
+/// use vtx_sdk::db::{DbValue, Json, TryToDbValue};
+///
+/// let tags = Json(vec!["rust", "wasm"]);
+/// assert!(matches!(
+///     tags.try_to_db_value(),
+///     Ok(DbValue::Text(ref s)) if s == r#"["rust","wasm"]"#
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T> std::ops::Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// 序列化失败时返回 `Internal`
+impl<T: Serialize> TryToDbValue for Json<T> {
+    fn try_to_db_value(&self) -> VtxResult<DbValue> {
+        json_column(&self.0)
+    }
+}
+
+impl<T: Serialize> TryToDbValue for &Json<T> {
+    fn try_to_db_value(&self) -> VtxResult<DbValue> {
+        (**self).try_to_db_value()
+    }
+}
+
+impl<T: DeserializeOwned> FromColumn for Json<T> {
+    fn from_column(value: &Value) -> Result<Self, String> {
+        let result = match value {
+            Value::String(text) => serde_json::from_str(text),
+            other => serde_json::from_value(other.clone()),
+        };
+        result.map(Json).map_err(|e| e.to_string())
+    }
+}
+
+// --- chrono ---
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{encode_error, DbValue, ToDbValue, TryToDbValue};
+    use crate::error::VtxResult;
+    use crate::host::db::row::FromColumn;
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+    use serde_json::Value;
+
+    /// 读取时接受任意小数位
+    const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+    /// 写入时使用定宽格式（固定 6 位小数）
+    const DATETIME_FORMAT_FIXED: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+    /// 年份超出 `0000..=9999` 时无法保持定宽，返回错误
+    fn check_year(date: &impl Datelike) -> VtxResult<()> {
+        if (0..=9999).contains(&date.year()) {
+            Ok(())
+        } else {
+            Err(encode_error(format_args!(
+                "year {} is outside 0000..=9999",
+                date.year()
+            )))
+        }
+    }
+
+    /// 转换为 UTC 后的定宽文本（如 `2024-05-01 08:30:00.000000`）；年份超出 `0000..=9999` 时报错
+    ///
+    /// ```rust
+    /// use chrono::{FixedOffset, TimeZone};
+    /// use vtx_sdk::db::{DbValue, TryToDbValue};
+    ///
+    /// let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+    /// let dt = tz.with_ymd_and_hms(2024, 5, 1, 16, 30, 0).unwrap();
+    /// assert!(matches!(dt.try_to_db_value(), Ok(DbValue::Text(ref s)) if s == "2024-05-01 08:30:00.000000"));
+    /// ```
+    impl<Tz: TimeZone> TryToDbValue for DateTime<Tz> {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            self.with_timezone(&Utc).naive_utc().try_to_db_value()
+        }
+    }
+
+    impl<Tz: TimeZone> TryToDbValue for &DateTime<Tz> {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    /// 定宽文本；年份超出 `0000..=9999` 时报错
+    impl TryToDbValue for NaiveDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            check_year(self)?;
+            Ok(DbValue::Text(
+                self.format(DATETIME_FORMAT_FIXED).to_string(),
+            ))
+        }
+    }
+
+    impl TryToDbValue for &NaiveDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    /// 定宽文本；年份超出 `0000..=9999` 时报错
+    impl TryToDbValue for NaiveDate {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            check_year(self)?;
+            Ok(DbValue::Text(self.format("%Y-%m-%d").to_string()))
+        }
+    }
+
+    impl TryToDbValue for &NaiveDate {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    impl ToDbValue for NaiveTime {
+        fn to_db_value(&self) -> DbValue {
+            DbValue::Text(self.format("%H:%M:%S%.6f").to_string())
+        }
+    }
+
+    fn parse_naive_datetime(text: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(text, DATETIME_FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f"))
+            .ok()
+    }
+
+    /// 接受 RFC 3339、SQLite `datetime()` 格式（视为 UTC）与 Unix 秒
+    impl FromColumn for DateTime<Utc> {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            let parsed = match value {
+                Value::String(text) => DateTime::parse_from_rfc3339(text)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .ok()
+                    .or_else(|| parse_naive_datetime(text).map(|dt| dt.and_utc())),
+                Value::Number(n) => n
+                    .as_i64()
+                    .and_then(|secs| Utc.timestamp_opt(secs, 0).single()),
+                _ => None,
+            };
+            parsed.ok_or_else(|| format!("expected datetime, found {}", value))
+        }
+    }
+
+    impl FromColumn for NaiveDateTime {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(parse_naive_datetime)
+                .ok_or_else(|| format!("expected datetime, found {}", value))
+        }
+    }
+
+    impl FromColumn for NaiveDate {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(|text| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok())
+                .ok_or_else(|| format!("expected date, found {}", value))
+        }
+    }
+
+    impl FromColumn for NaiveTime {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(|text| NaiveTime::parse_from_str(text, "%H:%M:%S%.f").ok())
+                .ok_or_else(|| format!("expected time, found {}", value))
+        }
+    }
+}
+
+// --- time ---
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::{encode_error, DbValue, ToDbValue, TryToDbValue};
+    use crate::error::VtxResult;
+    use crate::host::db::row::FromColumn;
+    use serde_json::Value;
+    use time::format_description::well_known::{Iso8601, Rfc3339};
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    /// 定宽日期文本；年份超出 `0000..=9999` 时无法保持定宽，返回错误
+    fn format_date(date: Date) -> VtxResult<String> {
+        if !(0..=9999).contains(&date.year()) {
+            return Err(encode_error(format_args!(
+                "year {} is outside 0000..=9999",
+                date.year()
+            )));
+        }
+        Ok(format!(
+            "{:04}-{:02}-{:02}",
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        ))
+    }
+
+    /// 定宽时间文本（固定 6 位小数，精确到微秒）
+    fn format_time(time: Time) -> String {
+        format!(
+            "{:02}:{:02}:{:02}.{:06}",
+            time.hour(),
+            time.minute(),
+            time.second(),
+            time.microsecond()
+        )
+    }
+
+    fn format_datetime(dt: PrimitiveDateTime) -> VtxResult<String> {
+        Ok(format!(
+            "{} {}",
+            format_date(dt.date())?,
+            format_time(dt.time())
+        ))
+    }
+
+    /// 转换为 UTC 后的定宽文本（如 `2024-05-01 08:30:00.000000`）；年份超出 `0000..=9999` 时报错
+    impl TryToDbValue for OffsetDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            let utc = self.to_offset(UtcOffset::UTC);
+            format_datetime(PrimitiveDateTime::new(utc.date(), utc.time())).map(DbValue::Text)
+        }
+    }
+
+    impl TryToDbValue for &OffsetDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    /// 定宽文本；年份超出 `0000..=9999` 时报错
+    impl TryToDbValue for PrimitiveDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            format_datetime(*self).map(DbValue::Text)
+        }
+    }
+
+    impl TryToDbValue for &PrimitiveDateTime {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    /// 定宽文本；年份超出 `0000..=9999` 时报错
+    impl TryToDbValue for Date {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            format_date(*self).map(DbValue::Text)
+        }
+    }
+
+    impl TryToDbValue for &Date {
+        fn try_to_db_value(&self) -> VtxResult<DbValue> {
+            (**self).try_to_db_value()
+        }
+    }
+
+    impl ToDbValue for Time {
+        fn to_db_value(&self) -> DbValue {
+            DbValue::Text(format_time(*self))
+        }
+    }
+
+    fn parse_primitive(text: &str) -> Option<PrimitiveDateTime> {
+        PrimitiveDateTime::parse(&text.replacen(' ', "T", 1), &Iso8601::DEFAULT).ok()
+    }
+
+    /// 接受 RFC 3339、SQLite `datetime()` 格式（视为 UTC）与 Unix 秒
+    impl FromColumn for OffsetDateTime {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            let parsed = match value {
+                Value::String(text) => OffsetDateTime::parse(text, &Rfc3339)
+                    .ok()
+                    .or_else(|| parse_primitive(text).map(PrimitiveDateTime::assume_utc)),
+                Value::Number(n) => n
+                    .as_i64()
+                    .and_then(|secs| OffsetDateTime::from_unix_timestamp(secs).ok()),
+                _ => None,
+            };
+            parsed.ok_or_else(|| format!("expected datetime, found {}", value))
+        }
+    }
+
+    impl FromColumn for PrimitiveDateTime {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(parse_primitive)
+                .ok_or_else(|| format!("expected datetime, found {}", value))
+        }
+    }
+
+    impl FromColumn for Date {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(|text| {
+                    PrimitiveDateTime::parse(&format!("{}T00:00:00", text), &Iso8601::DEFAULT).ok()
+                })
+                .map(PrimitiveDateTime::date)
+                .ok_or_else(|| format!("expected date, found {}", value))
+        }
+    }
+
+    impl FromColumn for Time {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            value
+                .as_str()
+                .and_then(|text| {
+                    PrimitiveDateTime::parse(&format!("2000-01-01T{}", text), &Iso8601::DEFAULT)
+                        .ok()
+                })
+                .map(PrimitiveDateTime::time)
+                .ok_or_else(|| format!("expected time, found {}", value))
+        }
+    }
+}
+
+// --- uuid ---
+
+#[cfg(feature = "uuid")]
+mod uuid_impls {
+    use super::{DbValue, ToDbValue};
+    use crate::host::db::row::FromColumn;
+    use serde_json::Value;
+    use uuid::Uuid;
+
+    impl ToDbValue for Uuid {
+        fn to_db_value(&self) -> DbValue {
+            DbValue::Text(self.hyphenated().to_string())
+        }
+    }
+
+    /// 接受文本形式与 16 字节 BLOB
+    impl FromColumn for Uuid {
+        fn from_column(value: &Value) -> Result<Self, String> {
+            match value {
+                Value::String(text) => Uuid::parse_str(text).map_err(|e| e.to_string()),
+                Value::Array(_) => {
+                    let bytes = Vec::<u8>::from_column(value)?;
+                    Uuid::from_slice(&bytes).map_err(|e| e.to_string())
+                }
+                _ => Err(format!("expected uuid, found {}", value)),
+            }
+        }
+    }
+}
@@ -1,16 +1,17 @@
 //! Host-side event bus helpers.
 
-use crate::bindings::vtx::api::event_bus;
+use crate::error::host::{HostError, HostErrorKind};
 use crate::error::{VtxError, VtxResult};
+use crate::imports::event_bus;
 
 /// 发布一个事件（payload 为已序列化的 JSON 字符串）。
 pub fn publish_raw(topic: &str, payload_json: &str) -> VtxResult<()> {
     event_bus::publish_event(topic, payload_json).map_err(|e| {
-        // 宿主侧会先做 JSON 校验，常见错误是 Invalid event payload
-        if e.to_lowercase().contains("invalid event payload") {
-            VtxError::SerializationError(e)
-        } else {
-            VtxError::from_host_message(e)
+        // 宿主侧会先做 JSON 校验，参数错误即 payload 不是合法 JSON
+        let err = HostError::parse(&e);
+        match err.kind {
+            HostErrorKind::InvalidInput => VtxError::SerializationError(err.message),
+            _ => err.into_vtx_error(VtxError::Internal),
         }
     })
 }
@@ -1,9 +1,9 @@
 //! Host-side FFmpeg helpers.
 
-use crate::bindings::vtx::api::ffmpeg::{self, FfmpegOption, TranscodeProfile};
-use crate::bindings::vtx::api::stream_io::Buffer;
-use crate::bindings::vtx::api::types::HttpResponse;
 use crate::error::{VtxError, VtxResult};
+use crate::http::{Response, ResponseBuilder};
+use crate::imports::ffmpeg::{self, FfmpegOption, TranscodeProfile};
+use crate::imports::stream_io::Buffer;
 
 /// FFmpeg task builder for running host-side transcoding.
 ///
@@ -103,11 +103,8 @@ impl FfmpegTask {
     }
 
     /// Execute and return an HTTP response (`200` with stdout pipe body).
-    pub fn execute(self) -> VtxResult<HttpResponse> {
+    pub fn execute(self) -> VtxResult<Response> {
         let buffer = self.execute_buffer()?;
-        Ok(HttpResponse {
-            status: 200,
-            body: Some(buffer),
-        })
+        Ok(ResponseBuilder::new(200).body_buffer(buffer).build())
     }
 }
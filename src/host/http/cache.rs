@@ -0,0 +1,380 @@
+//! Conditional requests and caching headers.
+
+use super::{content_type, Request, Response, ResponseBuilder};
+use crate::error::VtxResult;
+use crate::hash::fnv1a_64;
+use std::fmt;
+
+// =====================
+// ETag
+// =====================
+
+/// 根据内容字节计算强 ETag（如 `"1a-9f0c3d2e5b7a1c44"`）
+///
+/// 使用 FNV-1a 64 位哈希并附带长度，结果在不同构建与宿主之间保持稳定；
+/// 仅用于缓存校验，不具备抗碰撞的安全属性。
+pub fn strong_etag(bytes: &[u8]) -> String {
+    format!("\"{:x}-{:016x}\"", bytes.len(), fnv1a_64(bytes))
+}
+
+/// 根据调用方提供的版本号生成强 ETag（如 `"v42"`）
+pub fn version_etag(version: impl fmt::Display) -> String {
+    format!("\"{}\"", sanitize_etag(&version.to_string()))
+}
+
+/// 根据调用方提供的版本号生成弱 ETag（如 `W/"v42"`）
+pub fn weak_etag(version: impl fmt::Display) -> String {
+    format!("W/\"{}\"", sanitize_etag(&version.to_string()))
+}
+
+/// ETag 的 opaque-tag 不允许包含双引号与控制字符
+fn sanitize_etag(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| *c != '"' && !c.is_control())
+        .collect()
+}
+
+/// 去掉弱标记 `W/` 后的 opaque-tag
+fn opaque_tag(etag: &str) -> &str {
+    etag.trim().trim_start_matches("W/")
+}
+
+/// 弱比较（RFC 9110 §8.8.3.2）：忽略 `W/` 前缀后 opaque-tag 相同即匹配
+fn weak_match(a: &str, b: &str) -> bool {
+    opaque_tag(a) == opaque_tag(b)
+}
+
+/// 判断 `If-None-Match` 列表是否命中当前 ETag
+fn if_none_match_hits(header: &str, etag: &str) -> bool {
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || weak_match(candidate, etag))
+}
+
+// =====================
+// Cache-Control
+// =====================
+
+/// `Cache-Control` 响应头构造器
+///
+/// ```rust
+/// use vtx_sdk::http::cache::CacheControl;
+///
+/// let cc = CacheControl::new().public().max_age(86400).immutable();
+/// assert_eq!(cc.to_string(), "public, max-age=86400, immutable");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    directives: Vec<String>,
+}
+
+impl CacheControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn directive(mut self, value: impl Into<String>) -> Self {
+        self.directives.push(value.into());
+        self
+    }
+
+    pub fn public(self) -> Self {
+        self.directive("public")
+    }
+
+    pub fn private(self) -> Self {
+        self.directive("private")
+    }
+
+    /// 允许缓存但每次使用前必须重新校验
+    pub fn no_cache(self) -> Self {
+        self.directive("no-cache")
+    }
+
+    pub fn no_store(self) -> Self {
+        self.directive("no-store")
+    }
+
+    pub fn max_age(self, seconds: u64) -> Self {
+        self.directive(format!("max-age={}", seconds))
+    }
+
+    pub fn s_maxage(self, seconds: u64) -> Self {
+        self.directive(format!("s-maxage={}", seconds))
+    }
+
+    pub fn must_revalidate(self) -> Self {
+        self.directive("must-revalidate")
+    }
+
+    /// 内容在有效期内不会变化（适用于带版本号的缩略图、分片等）
+    pub fn immutable(self) -> Self {
+        self.directive("immutable")
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.directives.join(", "))
+    }
+}
+
+// =====================
+// HTTP-date
+// =====================
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// 将 Unix 时间戳（秒）格式化为 IMF-fixdate（如 `Sun, 06 Nov 1994 08:49:37 GMT`）
+///
+/// ```rust
+/// use vtx_sdk::http::cache::{http_date, parse_http_date};
+///
+/// assert_eq!(http_date(784111777), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(784111777));
+/// assert_eq!(parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"), Some(1709164800));
+/// assert_eq!(parse_http_date("Thu, 31 Feb 2024 00:00:00 GMT"), None);
+/// assert_eq!(parse_http_date("Sat, 29 Feb 2025 00:00:00 GMT"), None);
+/// ```
+pub fn http_date(unix_secs: u64) -> String {
+    let days = unix_secs / 86_400;
+    let secs = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// 解析 IMF-fixdate 为 Unix 时间戳（秒）；不支持的格式或不存在的日期返回 `None`
+pub fn parse_http_date(value: &str) -> Option<u64> {
+    // "Sun, 06 Nov 1994 08:49:37 GMT"
+    let (_, rest) = value.trim().split_once(", ")?;
+    let mut parts = rest.split(' ');
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut hms = parts.next()?.split(':').map(|p| p.parse::<u64>().ok());
+    let (h, m, s) = (hms.next()??, hms.next()??, hms.next()??);
+    if parts.next()? != "GMT" || h > 23 || m > 59 || s > 60 || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // 往返换算以拒绝不存在的日期（如 31 Feb、平年的 29 Feb）
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) {
+        return None;
+    }
+    u64::try_from(days)
+        .ok()
+        .map(|d| d * 86_400 + h * 3600 + m * 60 + s)
+}
+
+/// 公历日期 → 自 1970-01-01 起的天数（Howard Hinnant 算法）
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (i64::from(m) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// 自 1970-01-01 起的天数 → 公历日期
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+// =====================
+// 条件请求
+// =====================
+
+/// 条件请求的评估结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precondition {
+    /// 需要返回完整响应
+    Modified,
+    /// 客户端缓存仍然有效（GET / HEAD → 304）
+    NotModified,
+    /// 非安全方法的前置条件失败（412）
+    Failed,
+}
+
+/// 缓存校验器：描述资源的校验信息（ETag / Last-Modified）与缓存策略
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::http::cache::{CacheControl, Validators};
+/// use vtx_sdk::prelude::*;
+///
+/// fn thumbnail(req: &Request, uuid: &str, version: u64) -> Response {
+///     Validators::new()
+///         .version(version)
+///         .cache_control(CacheControl::new().public().max_age(3600))
+///         .respond(req, || ResponseBuilder::file(uuid))
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Validators {
+    etag: Option<String>,
+    last_modified: Option<u64>,
+    cache_control: Option<CacheControl>,
+}
+
+impl Validators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 直接指定 ETag（需包含引号，如 `"v1"` 或 `W/"v1"`）
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// 根据内容字节计算强 ETag
+    pub fn etag_from_bytes(self, bytes: &[u8]) -> Self {
+        self.etag(strong_etag(bytes))
+    }
+
+    /// 根据版本号生成强 ETag
+    pub fn version(self, version: impl fmt::Display) -> Self {
+        self.etag(version_etag(version))
+    }
+
+    /// 根据版本号生成弱 ETag（语义等价但字节可能不同的表示，如压缩前后）
+    pub fn weak_version(self, version: impl fmt::Display) -> Self {
+        self.etag(weak_etag(version))
+    }
+
+    /// 资源最后修改时间（Unix 秒）
+    pub fn last_modified(mut self, unix_secs: u64) -> Self {
+        self.last_modified = Some(unix_secs);
+        self
+    }
+
+    pub fn cache_control(mut self, cache_control: CacheControl) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    pub fn etag_value(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// 评估 `If-None-Match` / `If-Modified-Since`（RFC 9110 §13.2.2）
+    ///
+    /// 同时存在时 `If-None-Match` 优先；`If-Modified-Since` 仅对 GET / HEAD 生效。
+    pub fn evaluate(&self, req: &Request) -> Precondition {
+        let safe =
+            req.method.eq_ignore_ascii_case("GET") || req.method.eq_ignore_ascii_case("HEAD");
+
+        if let Some(header) = req.header("If-None-Match") {
+            let hit = match &self.etag {
+                Some(etag) => if_none_match_hits(header, etag),
+                None => header.trim() == "*",
+            };
+            return match (hit, safe) {
+                (false, _) => Precondition::Modified,
+                (true, true) => Precondition::NotModified,
+                (true, false) => Precondition::Failed,
+            };
+        }
+
+        if safe {
+            let since = req.header("If-Modified-Since").and_then(parse_http_date);
+            if let (Some(since), Some(modified)) = (since, self.last_modified) {
+                if modified <= since {
+                    return Precondition::NotModified;
+                }
+            }
+        }
+
+        Precondition::Modified
+    }
+
+    /// 为响应附加 `ETag` / `Last-Modified` / `Cache-Control`
+    pub fn apply(&self, mut resp: Response) -> Response {
+        if let Some(etag) = &self.etag {
+            resp.set_header("ETag", etag.clone());
+        }
+        if let Some(ts) = self.last_modified {
+            resp.set_header("Last-Modified", http_date(ts));
+        }
+        if let Some(cc) = &self.cache_control {
+            resp.set_header("Cache-Control", cc.to_string());
+        }
+        resp
+    }
+
+    /// 304 Not Modified 响应（携带校验头，无正文）
+    pub fn not_modified(&self) -> Response {
+        self.apply(ResponseBuilder::status(304))
+    }
+
+    /// 评估条件请求：命中缓存时短路返回 304 / 412，否则调用 `f` 生成完整响应
+    ///
+    /// 缓存头仅附加到 2xx / 304 响应；`f` 返回的错误响应（如文件不存在的 404）原样返回，
+    /// 避免错误被缓存或与资源的 ETag 关联。
+    pub fn respond(&self, req: &Request, f: impl FnOnce() -> Response) -> Response {
+        match self.evaluate(req) {
+            Precondition::NotModified => self.not_modified(),
+            Precondition::Failed => ResponseBuilder::status(412),
+            Precondition::Modified => {
+                let resp = f();
+                if (200..300).contains(&resp.status) || resp.status == 304 {
+                    self.apply(resp)
+                } else {
+                    resp
+                }
+            }
+        }
+    }
+}
+
+impl ResponseBuilder {
+    /// 构造带强 ETag 的 JSON 响应，并处理 `If-None-Match`（命中时返回 304）
+    ///
+    /// ETag 基于序列化后的字节计算，序列化失败时返回 500 `serialization_error`。
+    pub fn json_cached<T: serde::Serialize>(
+        req: &Request,
+        data: &T,
+        cache_control: CacheControl,
+    ) -> VtxResult<Response> {
+        let bytes = serde_json::to_vec(data).map_err(super::response_serialization_error)?;
+
+        let validators = Validators::new()
+            .etag_from_bytes(&bytes)
+            .cache_control(cache_control);
+
+        Ok(validators.respond(req, || {
+            ResponseBuilder::new(200)
+                .content_type(content_type::JSON)
+                .body(bytes)
+                .build()
+        }))
+    }
+}
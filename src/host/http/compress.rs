@@ -0,0 +1,324 @@
+//! Response compression negotiated via `Accept-Encoding`.
+//!
+//! 编码器均为纯 Rust 实现（可编译到 Wasm），按 feature 启用：
+//!
+//! | feature | `Content-Encoding` | 实现 |
+//! |---------|--------------------|------|
+//! | `brotli` | `br` | `brotli` |
+//! | `zstd` | `zstd` | `ruzstd` |
+//! | `gzip` | `gzip` | `flate2`（`miniz_oxide` 后端） |
+//!
+//! 未启用任何编码 feature 时 `Compression::apply` 原样返回响应。
+//! 以下响应不会压缩：已设置 `Content-Encoding`、非 2xx 或 206、媒体类型不可压缩
+//! （图片、音视频、压缩包等）、body 小于 `min_size` 或大于 `max_size`、pipe body（长度未知）。
+//!
+//! 仅在请求携带 `Accept-Encoding` 时压缩：协议 3.6 的宿主既不传递请求头也不转发响应头，
+//! 此时响应保持原样，不会出现缺少 `Content-Encoding` 的压缩 body。
+//!
+//! ```rust
+//! use vtx_sdk::http::compress::Compression;
+//! use vtx_sdk::prelude::*;
+//! use vtx_sdk::router::Router;
+//!
+//! fn routes() -> Router {
+//!     Router::new()
+//!         .get("/catalog", |_req: &Request, _params: &Params| {
+//!             Ok(ResponseBuilder::json(&vec!["..."; 1000]))
+//!         })
+//!         .compression(Compression::new().min_size(512))
+//! }
+//! ```
+
+use super::{Request, Response};
+use crate::imports::stream_io;
+use crate::stream::BufferExt;
+
+/// 小于该字节数的 body 不压缩
+pub const DEFAULT_MIN_SIZE: u64 = 1024;
+/// 大于该字节数的 body 不压缩（压缩需要将 body 读入插件内存）
+pub const DEFAULT_MAX_SIZE: usize = 16 * 1024 * 1024;
+
+/// 内容编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Brotli,
+    Zstd,
+    Gzip,
+}
+
+impl Encoding {
+    /// 协商时的默认优先顺序（权重相同时靠前者优先）
+    pub const ALL: [Encoding; 3] = [Encoding::Brotli, Encoding::Zstd, Encoding::Gzip];
+
+    /// `Content-Encoding` 取值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Zstd => "zstd",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    /// 对应的 feature 是否已启用
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Brotli => cfg!(feature = "brotli"),
+            Self::Zstd => cfg!(feature = "zstd"),
+            Self::Gzip => cfg!(feature = "gzip"),
+        }
+    }
+
+    /// 压缩数据（feature 未启用时返回 `None`）
+    pub fn compress(self, data: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(feature = "brotli")]
+            Self::Brotli => brotli_compress(data),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Some(ruzstd::encoding::compress_to_vec(
+                data,
+                ruzstd::encoding::CompressionLevel::Fastest,
+            )),
+            #[cfg(feature = "gzip")]
+            Self::Gzip => gzip_compress(data),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = data;
+                None
+            }
+        }
+    }
+
+    fn matches(self, token: &str) -> bool {
+        token.eq_ignore_ascii_case(self.as_str())
+            || (self == Self::Gzip && token.eq_ignore_ascii_case("x-gzip"))
+    }
+}
+
+#[cfg(feature = "brotli")]
+fn brotli_compress(data: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Write;
+
+    // quality 5 / window 22：动态内容常用的速度与压缩率折中
+    let mut writer = brotli::CompressorWriter::new(Vec::new(), 64 * 1024, 5, 22);
+    writer.write_all(data).ok()?;
+    writer.flush().ok()?;
+    Some(writer.into_inner())
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_compress(data: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).ok()?;
+    encoder.finish().ok()
+}
+
+/// 按 `Accept-Encoding` 从 `candidates` 中选择编码
+///
+/// 选择权重（`q`）最高者，权重相同时按 `candidates` 顺序；`q=0` 表示拒绝，
+/// 未列出的编码使用 `*` 的权重（未列出 `*` 时视为不接受）。
+///
+/// ```rust
+/// use vtx_sdk::http::compress::{negotiate, Encoding};
+///
+/// let all = Encoding::ALL;
+/// assert_eq!(negotiate("gzip, deflate, br", &all), Some(Encoding::Brotli));
+/// assert_eq!(negotiate("br;q=0.5, gzip", &all), Some(Encoding::Gzip));
+/// assert_eq!(negotiate("*;q=0.1, br;q=0", &all), Some(Encoding::Zstd));
+/// assert_eq!(negotiate("identity", &all), None);
+/// ```
+pub fn negotiate(accept_encoding: &str, candidates: &[Encoding]) -> Option<Encoding> {
+    let entries: Vec<(&str, f32)> = accept_encoding
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let token = parts.next()?.trim();
+            if token.is_empty() {
+                return None;
+            }
+            let q = parts
+                .filter_map(|p| p.trim().split_once('='))
+                .find(|(k, _)| k.trim().eq_ignore_ascii_case("q"))
+                .map_or(Some(1.0), |(_, v)| v.trim().parse::<f32>().ok())?;
+            Some((token, q))
+        })
+        .collect();
+
+    let wildcard = entries
+        .iter()
+        .find(|(token, _)| *token == "*")
+        .map(|(_, q)| *q);
+
+    let mut best: Option<(Encoding, f32)> = None;
+    for &encoding in candidates {
+        let q = entries
+            .iter()
+            .find(|(token, _)| encoding.matches(token))
+            .map(|(_, q)| *q)
+            .or(wildcard)
+            .unwrap_or(0.0);
+        if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+            best = Some((encoding, q));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+/// 媒体类型是否值得压缩（文本类；图片、音视频、压缩包等已压缩格式返回 `false`）
+///
+/// ```rust
+/// use vtx_sdk::http::compress::is_compressible;
+///
+/// assert!(is_compressible("application/json"));
+/// assert!(is_compressible("text/html; charset=utf-8"));
+/// assert!(is_compressible("image/svg+xml"));
+/// assert!(!is_compressible("image/png"));
+/// assert!(!is_compressible("video/mp4"));
+/// ```
+pub fn is_compressible(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json"
+                | "application/x-ndjson"
+                | "application/javascript"
+                | "application/xml"
+                | "application/wasm"
+                | "application/vnd.apple.mpegurl"
+                | "application/x-mpegurl"
+        )
+}
+
+/// 响应压缩配置
+#[derive(Debug, Clone)]
+pub struct Compression {
+    min_size: u64,
+    max_size: usize,
+    encodings: Vec<Encoding>,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compression {
+    /// 默认配置：启用全部已编译的编码（`br` > `zstd` > `gzip`）
+    pub fn new() -> Self {
+        Self {
+            min_size: DEFAULT_MIN_SIZE,
+            max_size: DEFAULT_MAX_SIZE,
+            encodings: Encoding::ALL
+                .into_iter()
+                .filter(|e| e.is_available())
+                .collect(),
+        }
+    }
+
+    /// 小于该字节数的 body 不压缩
+    pub fn min_size(mut self, bytes: u64) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// 大于该字节数的 body 不压缩
+    pub fn max_size(mut self, bytes: usize) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// 允许的编码及其优先顺序（未启用 feature 的编码会被忽略）
+    pub fn encodings(mut self, encodings: &[Encoding]) -> Self {
+        self.encodings = encodings
+            .iter()
+            .copied()
+            .filter(|e| e.is_available())
+            .collect();
+        self
+    }
+
+    /// 按请求的 `Accept-Encoding` 压缩响应；不满足条件时原样返回
+    ///
+    /// 可压缩的响应总会追加 `Vary: Accept-Encoding`；压缩后强 `ETag` 转为弱 `ETag`。
+    pub fn apply(&self, req: &Request, mut resp: Response) -> Response {
+        if !(200..300).contains(&resp.status)
+            || resp.status == 206
+            || resp.header("Content-Encoding").is_some()
+            || resp.header("Content-Range").is_some()
+            || !resp.header("Content-Type").is_some_and(is_compressible)
+        {
+            return resp;
+        }
+        if resp.body.is_none() {
+            return resp;
+        }
+        add_vary(&mut resp);
+
+        let Some(body) = &resp.body else {
+            return resp;
+        };
+        let size = body.size();
+        if size == 0 || size < self.min_size || size > self.max_size as u64 {
+            return resp;
+        }
+        let Some(encoding) = req
+            .header("Accept-Encoding")
+            .and_then(|accept| negotiate(accept, &self.encodings))
+        else {
+            return resp;
+        };
+        let Ok(data) = body.read_all_limited(self.max_size) else {
+            return resp;
+        };
+        let Some(compressed) = encoding.compress(&data) else {
+            return resp;
+        };
+        if compressed.len() >= data.len() {
+            return resp;
+        }
+
+        resp.body = Some(stream_io::create_memory_buffer(&compressed));
+        resp.set_header("Content-Encoding", encoding.as_str());
+        resp.remove_header("Content-Length");
+        if let Some(etag) = resp.header("ETag").filter(|e| e.starts_with('"')) {
+            let weak = format!("W/{}", etag);
+            resp.set_header("ETag", weak);
+        }
+        resp
+    }
+}
+
+/// 追加 `Vary: Accept-Encoding`（已包含或为 `*` 时不重复添加）
+fn add_vary(resp: &mut Response) {
+    let vary = match resp.header("Vary") {
+        Some(existing)
+            if existing.split(',').any(|v| {
+                let v = v.trim();
+                v == "*" || v.eq_ignore_ascii_case("Accept-Encoding")
+            }) =>
+        {
+            return;
+        }
+        Some(existing) if !existing.trim().is_empty() => format!("{}, Accept-Encoding", existing),
+        _ => "Accept-Encoding".to_string(),
+    };
+    resp.set_header("Vary", vary);
+}
+
+impl Response {
+    /// 使用默认配置按请求压缩响应（详见 `Compression`）
+    pub fn compressed(self, req: &Request) -> Response {
+        Compression::new().apply(req, self)
+    }
+}
@@ -0,0 +1,46 @@
+//! `application/x-www-form-urlencoded` request bodies.
+
+use super::{parse_urlencoded, Request};
+use crate::error::{VtxError, VtxResult};
+use crate::router::extract::from_pairs;
+use crate::stream::BufferExt;
+use serde::de::DeserializeOwned;
+
+/// 表单请求体默认的最大字节数（2 MiB）
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// 将 URL 编码的表单字符串反序列化为目标类型
+///
+/// 字段值按目标类型解析（数字、布尔、`Option` 等），规则与 `Query<T>` 相同。
+///
+/// ```rust
+/// use vtx_sdk::http::form;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Login {
+///     user: String,
+///     remember: Option<bool>,
+/// }
+///
+/// let login: Login = form::from_urlencoded("user=alice%40example.com&remember=true")?;
+/// assert_eq!(login.user, "alice@example.com");
+/// assert_eq!(login.remember, Some(true));
+/// # Ok::<(), vtx_sdk::error::VtxError>(())
+/// ```
+pub fn from_urlencoded<T: DeserializeOwned>(input: &str) -> VtxResult<T> {
+    from_pairs(&parse_urlencoded(input))
+        .map_err(|e| VtxError::BadRequest(format!("Invalid form body: {}", e)))
+}
+
+impl Request {
+    /// 将请求体按 URL 编码表单解析（最多 `DEFAULT_BODY_LIMIT` 字节，超过时返回 413）
+    ///
+    /// ⚠️ 协议 3.6 的宿主不传递请求体，此时返回 501 `unsupported_by_host`
+    /// （见 `http::HOST_FORWARDS_REQUEST_HEADERS`）；`from_urlencoded` 不受影响。
+    pub fn form<T: DeserializeOwned>(&self) -> VtxResult<T> {
+        let bytes = self.require_body()?.read_all_limited(DEFAULT_BODY_LIMIT)?;
+        let text = String::from_utf8(bytes)
+            .map_err(|_| VtxError::BadRequest("Form body is not valid UTF-8".into()))?;
+        from_urlencoded(&text)
+    }
+}
@@ -0,0 +1,466 @@
+//! Host-side HTTP request/response helpers.
+
+/// WIT 原始请求 / 响应类型（0.1.x 中的 `Request` / `Response`）
+///
+/// 直接实现 `PluginTrait`（bindings `Guest`）时使用：入口处以 `Request::from(req)` 转换，
+/// 返回前以 `HttpResponse::from(resp)` 转换。
+pub use crate::bindings::vtx::api::types::{HttpRequest, HttpResponse};
+use crate::error::{ProblemDetails, VtxError, VtxResult};
+use crate::imports::stream_io::{self, Buffer};
+use crate::stream::BufferExt;
+use std::fmt;
+
+/// 条件请求（ETag / Last-Modified → 304 / 412）与缓存响应头
+pub mod cache;
+pub mod compress;
+pub mod form;
+pub mod multipart;
+pub mod ndjson;
+/// Range 请求（206 Partial Content）支持
+pub mod range;
+
+/// HTTP 请求（SDK 视图）
+///
+/// 在 WIT `http-request`（method / path / query）基础上预留 `headers` 与 `body`：
+/// - 协议 3.6 的宿主尚不传递请求头与请求体，此时二者分别为空列表与 `None`
+///   （见 `HOST_FORWARDS_REQUEST_HEADERS`）
+/// - 宿主协议升级或测试工具构造请求时可直接填充，插件代码无需改动
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// 原始查询字符串（不含 `?`）
+    pub query: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Buffer>,
+}
+
+impl From<HttpRequest> for Request {
+    fn from(req: HttpRequest) -> Self {
+        Self {
+            method: req.method,
+            path: req.path,
+            query: req.query,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+}
+
+/// 宿主是否传递请求头与请求体
+///
+/// 协议 3.6 的 `http-request` 只有 method / path / query，该值为 `false`：
+/// `Request.headers` 恒为空、`Request.body` 恒为 `None`，依赖二者的功能
+/// （`Json` / `Header` 提取器、Range、条件请求、表单、multipart、压缩）只能在
+/// `testing` feature 的模拟宿主下使用。
+pub const HOST_FORWARDS_REQUEST_HEADERS: bool = cfg!(vtx_mock_host);
+
+/// 响应数据序列化失败（500 `serialization_error`）
+///
+/// 与 `VtxError::SerializationError`（请求数据格式错误，400）区分：
+/// 响应无法序列化是插件自身的问题，不应归咎于客户端。
+pub fn response_serialization_error(message: impl fmt::Display) -> VtxError {
+    ProblemDetails::new(500, "serialization_error")
+        .detail(format!("Failed to serialize response: {}", message))
+        .into()
+}
+
+/// 宿主协议不支持某项功能时的错误（501 `unsupported_by_host`）
+pub(crate) fn unsupported_by_host(what: &str) -> VtxError {
+    ProblemDetails::new(501, "unsupported_by_host")
+        .detail(format!("{} are not forwarded by host protocol 3.6", what))
+        .into()
+}
+
+impl Request {
+    /// 获取请求体；宿主不传递请求体时返回 501 `unsupported_by_host`，
+    /// 模拟宿主下请求未携带请求体时返回 400
+    pub fn require_body(&self) -> VtxResult<&Buffer> {
+        match &self.body {
+            Some(body) => Ok(body),
+            None if !HOST_FORWARDS_REQUEST_HEADERS => Err(unsupported_by_host("Request bodies")),
+            None => Err(VtxError::BadRequest("Request body is empty".into())),
+        }
+    }
+
+    /// 获取 Header 值 (Case-insensitive)
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 解析查询字符串为键值对（已做 `+` 与百分号解码，保持原始顺序）
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        parse_urlencoded(&self.query)
+    }
+
+    /// 获取首个同名查询参数
+    pub fn query_param(&self, key: &str) -> Option<String> {
+        self.query_pairs()
+            .into_iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+}
+
+/// 解析 `application/x-www-form-urlencoded` 格式的键值对
+pub(crate) fn parse_urlencoded(input: &str) -> Vec<(String, String)> {
+    input
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                percent_decode(&k.replace('+', " ")),
+                percent_decode(&v.replace('+', " ")),
+            )
+        })
+        .collect()
+}
+
+/// 百分号解码（非法编码按原样保留，非 UTF-8 字节按 lossy 处理）
+pub(crate) fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // `from_str_radix` 接受前导 `+`，需先确认两位均为十六进制数字
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            out.push((hex_value(bytes[i + 1]) << 4) | hex_value(bytes[i + 2]));
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+/// 常用 `Content-Type` 取值
+pub mod content_type {
+    pub const JSON: &str = "application/json";
+    pub const FORM: &str = "application/x-www-form-urlencoded";
+    pub const NDJSON: &str = "application/x-ndjson";
+    pub const PROBLEM_JSON: &str = "application/problem+json";
+    pub const TEXT: &str = "text/plain; charset=utf-8";
+    pub const HTML: &str = "text/html; charset=utf-8";
+    pub const OCTET_STREAM: &str = "application/octet-stream";
+}
+
+/// HTTP 响应（SDK 视图）
+///
+/// 在 WIT `http-response`（status / body）基础上增加 `headers`：
+/// - 插件侧统一通过 `headers` 描述响应头，测试工具可直接断言
+/// - 在导出边界转换为 WIT 响应（`From<Response> for HttpResponse`）
+///
+/// ⚠️ 协议 3.6 的 `http-response` 没有 headers 字段，也没有其他可携带响应头的通道：
+/// 在真实宿主上响应头（包括 `Content-Type`）全部无法送达客户端
+/// （见 `HOST_FORWARDS_RESPONSE_HEADERS`）。依赖响应头才有意义的响应
+/// （`Location` 重定向、206 / 416 的 `Content-Range`、`Content-Encoding` 压缩、
+/// `Set-Cookie` 等）在真实宿主上不可用，转换时不会报错也不会输出任何提示。
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Buffer>,
+}
+
+/// 宿主是否将 `Response.headers` 转发给客户端（协议 3.6：否；仅模拟宿主下为 `true`）
+pub const HOST_FORWARDS_RESPONSE_HEADERS: bool = cfg!(vtx_mock_host);
+
+impl From<Response> for HttpResponse {
+    /// 导出边界转换：协议 3.6 下 `headers` 在此被丢弃
+    fn from(resp: Response) -> Self {
+        HttpResponse {
+            status: resp.status,
+            body: resp.body,
+        }
+    }
+}
+
+impl From<HttpResponse> for Response {
+    fn from(resp: HttpResponse) -> Self {
+        Self {
+            status: resp.status,
+            headers: Vec::new(),
+            body: resp.body,
+        }
+    }
+}
+
+impl Response {
+    /// 获取 Header 值 (Case-insensitive)
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 设置 Header（替换所有同名 Header）
+    pub fn set_header(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        let key = key.into();
+        self.headers.retain(|(k, _)| !k.eq_ignore_ascii_case(&key));
+        self.headers.push((key, value.into()));
+    }
+
+    /// 追加 Header（保留同名 Header，如多个 `Set-Cookie`）
+    pub fn append_header(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.headers.push((key.into(), value.into()));
+    }
+
+    /// 移除所有同名 Header
+    pub fn remove_header(&mut self, key: &str) {
+        self.headers.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+    }
+}
+
+/// HTTP 响应构造器（适用于插件运行时）
+///
+/// 提供以下构造能力：
+/// - 链式构造：`ResponseBuilder::new(status).header(..).content_type(..).body(..).build()`
+/// - 成功响应（JSON / 文本 / HTML / 二进制）
+/// - 错误响应（自动映射状态码与结构体）
+/// - 文件流响应（基于宿主 UUID 打开文件）
+/// - 状态码响应（纯状态码，无 body）及 204 / 400 等常用状态（依赖 `Location` 的 201 / 3xx 仅模拟宿主提供）
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::prelude::*;
+///
+/// fn handle_manifest() -> Response {
+///     ResponseBuilder::new(200)
+///         .content_type("application/vnd.apple.mpegurl")
+///         .header("Cache-Control", "no-cache")
+///         .body("#EXTM3U\n")
+///         .build()
+/// }
+/// ```
+pub struct ResponseBuilder {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Option<Buffer>,
+}
+
+impl ResponseBuilder {
+    /// 以指定状态码开始链式构造
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// 追加响应头
+    ///
+    /// ⚠️ 协议 3.6 的宿主不转发响应头（见 `HOST_FORWARDS_RESPONSE_HEADERS`）。
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// 设置 `Content-Type`（替换已有值）
+    ///
+    /// ⚠️ 协议 3.6 的宿主不转发响应头，`Content-Type` 由宿主自行推断。
+    pub fn content_type(mut self, value: impl Into<String>) -> Self {
+        self.headers
+            .retain(|(k, _)| !k.eq_ignore_ascii_case("Content-Type"));
+        self.headers
+            .push(("Content-Type".to_string(), value.into()));
+        self
+    }
+
+    /// 以字节内容作为响应体（写入宿主内存 Buffer）
+    pub fn body(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.body = Some(stream_io::create_memory_buffer(data.as_ref()));
+        self
+    }
+
+    /// 以已有 Buffer（文件 / pipe / memory）作为响应体
+    pub fn body_buffer(mut self, buffer: Buffer) -> Self {
+        self.body = Some(buffer);
+        self
+    }
+
+    /// 完成构造
+    pub fn build(self) -> Response {
+        Response {
+            status: self.status,
+            headers: self.headers,
+            body: self.body,
+        }
+    }
+
+    /// 构造 JSON 响应（200 OK）
+    ///
+    /// 若序列化失败，返回 500 错误响应（`code: serialization_error`），
+    /// 不会伪装成成功的空结果；需要自行处理失败时请使用 `try_json`。
+    pub fn json<T: serde::Serialize>(data: &T) -> Response {
+        Self::try_json(data).unwrap_or_else(Self::error)
+    }
+
+    /// 构造 JSON 响应（200 OK）
+    ///
+    /// 序列化失败属于服务端错误，返回 500 `serialization_error`（见 `response_serialization_error`），
+    /// 与 `json` 的失败响应一致。
+    pub fn try_json<T: serde::Serialize>(data: &T) -> VtxResult<Response> {
+        // 分块序列化到宿主内存缓冲区，不在插件内存中生成完整 JSON
+        let body = stream_io::create_memory_buffer(&[]);
+        body.write_json(data).map_err(|err| match err {
+            VtxError::SerializationError(msg) => response_serialization_error(msg),
+            other => other,
+        })?;
+
+        Ok(Self::new(200)
+            .content_type(content_type::JSON)
+            .body_buffer(body)
+            .build())
+    }
+
+    /// 构造 JSON 响应（200 OK，宽松模式）
+    ///
+    /// ⚠️ 若序列化失败，返回 `[]` 作为兜底内容，**不表示逻辑成功**。
+    /// 仅用于兼容依赖旧行为的客户端，新代码请使用 `json` / `try_json`。
+    pub fn json_lenient<T: serde::Serialize>(data: &T) -> Response {
+        let json_bytes = serde_json::to_vec(data).unwrap_or_else(|_| b"[]".to_vec());
+
+        Self::new(200)
+            .content_type(content_type::JSON)
+            .body(json_bytes)
+            .build()
+    }
+
+    /// 构造纯文本响应（200 OK，`text/plain; charset=utf-8`）
+    pub fn text(text: impl AsRef<str>) -> Response {
+        Self::new(200)
+            .content_type(content_type::TEXT)
+            .body(text.as_ref())
+            .build()
+    }
+
+    /// 构造 HTML 响应（200 OK，`text/html; charset=utf-8`）
+    pub fn html(html: impl AsRef<str>) -> Response {
+        Self::new(200)
+            .content_type(content_type::HTML)
+            .body(html.as_ref())
+            .build()
+    }
+
+    /// 构造二进制响应（200 OK，`application/octet-stream`）
+    pub fn bytes(data: impl AsRef<[u8]>) -> Response {
+        Self::new(200)
+            .content_type(content_type::OCTET_STREAM)
+            .body(data)
+            .build()
+    }
+
+    /// 构造 201 Created 响应（带 `Location`，无正文）
+    ///
+    /// ⚠️ 仅模拟宿主（`testing` feature）提供：协议 3.6 无法传递 `Location`，
+    /// 在真实宿主上只会得到缺少 `Location` 的 201。
+    #[cfg(any(vtx_mock_host, doc))]
+    pub fn created(location: impl Into<String>) -> Response {
+        Self::new(201).header("Location", location).build()
+    }
+
+    /// 构造 204 No Content 响应
+    pub fn no_content() -> Response {
+        Self::status(204)
+    }
+
+    /// 构造 302 Found 重定向响应
+    ///
+    /// ⚠️ 仅模拟宿主（`testing` feature）提供：协议 3.6 无法传递 `Location`，
+    /// 在真实宿主上只会得到无法跳转的 302。
+    #[cfg(any(vtx_mock_host, doc))]
+    pub fn redirect(location: impl Into<String>) -> Response {
+        Self::new(302).header("Location", location).build()
+    }
+
+    /// 构造 308 Permanent Redirect 重定向响应（保留请求方法）
+    ///
+    /// ⚠️ 仅模拟宿主（`testing` feature）提供，原因同 `redirect`。
+    #[cfg(any(vtx_mock_host, doc))]
+    pub fn permanent_redirect(location: impl Into<String>) -> Response {
+        Self::new(308).header("Location", location).build()
+    }
+
+    /// 构造 400 Bad Request 错误响应（正文结构同 `error`）
+    pub fn bad_request(message: impl Into<String>) -> Response {
+        Self::error(VtxError::BadRequest(message.into()))
+    }
+
+    /// 构造错误响应（`application/problem+json`，状态码见 `VtxError::status`）
+    ///
+    /// 返回结构（RFC 7807，`code` 为稳定错误码，`errors` 仅在存在字段错误时出现）：
+    /// ```json
+    /// {
+    ///   "type": "about:blank",
+    ///   "title": "Forbidden",
+    ///   "status": 403,
+    ///   "detail": "You are not allowed to access this resource",
+    ///   "code": "permission_denied"
+    /// }
+    /// ```
+    pub fn error(err: VtxError) -> Response {
+        Self::problem(&err.to_problem())
+    }
+
+    /// 构造 RFC 7807 问题详情响应
+    pub fn problem(problem: &ProblemDetails) -> Response {
+        // `ProblemDetails` 仅包含字符串、整数与 JSON 值，序列化不会失败；
+        // 使用 `Value` 兜底以避免与 `json` 的失败分支相互递归
+        let body = serde_json::to_vec(problem).unwrap_or_else(|_| {
+            serde_json::json!({ "status": problem.status, "code": problem.code })
+                .to_string()
+                .into_bytes()
+        });
+
+        Self::new(problem.status)
+            .content_type(content_type::PROBLEM_JSON)
+            .body(body)
+            .build()
+    }
+
+    /// 构造文件流响应（通过宿主接口按 UUID 打开）
+    ///
+    /// - 成功：200 + 文件内容流
+    /// - 失败：返回 404 JSON 错误响应
+    ///
+    /// 需要支持播放器拖动（Range 请求）时请使用 `file_ranged` / `range::FileResponse`。
+    pub fn file(uuid: &str) -> Response {
+        match stream_io::open_file(uuid) {
+            Ok(buffer) => Self::new(200).body_buffer(buffer).build(),
+            Err(e) => Self::error(VtxError::NotFound(format!("File UUID not found: {}", e))),
+        }
+    }
+
+    /// 构造纯状态码响应（无 body）
+    ///
+    /// 用于如：204 No Content、403 Forbidden 等响应场景
+    pub fn status(code: u16) -> Response {
+        Self::new(code).build()
+    }
+
+    /// 构造标准 404 Not Found 响应（无正文）
+    pub fn not_found() -> Response {
+        Self::status(404)
+    }
+}
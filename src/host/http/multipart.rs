@@ -0,0 +1,500 @@
+//! Streaming `multipart/form-data` parser.
+//!
+//! 逐个读取请求体中的 part，part 数据按需分块读取，上传文件无需整体载入插件内存：
+//!
+//! ```rust,no_run
+//! use vtx_sdk::http::multipart::Multipart;
+//! use vtx_sdk::prelude::*;
+//!
+//! fn upload(req: &Request) -> VtxResult<Response> {
+//!     let output = stream::memory_buffer([]);
+//!     let mut title = None;
+//!
+//!     let mut multipart = Multipart::from_request(req)?.max_part_size(512 * 1024 * 1024);
+//!     while let Some(mut part) = multipart.next_part()? {
+//!         match (part.name(), part.filename()) {
+//!             (Some("title"), _) => title = Some(part.text()?),
+//!             (Some("video"), Some(_)) => {
+//!                 part.copy_to(&output)?;
+//!             }
+//!             _ => {}
+//!         }
+//!     }
+//!     Ok(ResponseBuilder::json(&title))
+//! }
+//! ```
+//!
+//! 未读完的 part 会在调用 `next_part` 时自动跳过。文件名只保留最后一个路径分隔符之后的部分。
+//!
+//! ⚠️ 协议 3.6 的宿主既不传递请求体也不传递 `Content-Type`，`Multipart::from_request`
+//! 在真实宿主上返回 501 `unsupported_by_host`（见 `http::HOST_FORWARDS_REQUEST_HEADERS`）；
+//! 已有 Buffer 与 boundary 时（如解析存储的上传文件）可直接使用 `Multipart::new`。
+
+use super::{percent_decode, unsupported_by_host, Request, HOST_FORWARDS_REQUEST_HEADERS};
+use crate::error::{ProblemDetails, VtxError, VtxResult};
+use crate::imports::stream_io::Buffer;
+use crate::stream::{BufferExt, BufferReader};
+use std::borrow::Borrow;
+use std::io::{self, Read};
+
+/// 每个 part 默认的最大字节数（64 MiB）
+pub const DEFAULT_MAX_PART_SIZE: u64 = 64 * 1024 * 1024;
+/// 默认最多 part 数量
+pub const DEFAULT_MAX_PARTS: usize = 128;
+/// 每个 part 头部默认的最大字节数（16 KiB）
+pub const DEFAULT_MAX_HEADER_SIZE: usize = 16 * 1024;
+
+const CHUNK: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// 第一个分隔符之前（忽略）
+    Preamble,
+    /// part 头部
+    Headers,
+    /// part 数据
+    Body,
+    /// 已读到结束分隔符
+    Done,
+}
+
+/// `multipart/form-data` 解析器
+pub struct Multipart<B: Borrow<Buffer> = Buffer> {
+    reader: BufferReader<B>,
+    /// `\r\n--boundary`
+    delimiter: Vec<u8>,
+    pending: Vec<u8>,
+    pos: usize,
+    /// `pending[pos..scanned]` 已确认不含分隔符，可直接作为 part 数据返回
+    scanned: usize,
+    /// 已找到的下一个分隔符在 `pending` 中的位置
+    next_delimiter: Option<usize>,
+    state: State,
+    parts: usize,
+    part_len: u64,
+    max_parts: usize,
+    max_part_size: u64,
+    max_header_size: usize,
+}
+
+impl<'a> Multipart<&'a Buffer> {
+    /// 从请求的 `Content-Type` 中读取 boundary 并解析请求体
+    ///
+    /// ⚠️ 协议 3.6 的宿主不传递请求体与请求头，此时返回 501 `unsupported_by_host`。
+    pub fn from_request(req: &'a Request) -> VtxResult<Self> {
+        let body = req.require_body()?;
+        if !HOST_FORWARDS_REQUEST_HEADERS {
+            return Err(unsupported_by_host("Request headers"));
+        }
+        let content_type = req.header("Content-Type").unwrap_or_default();
+        let boundary = boundary(content_type).ok_or_else(|| {
+            VtxError::BadRequest("Expected multipart/form-data with a boundary".into())
+        })?;
+        Ok(Self::new(body, &boundary))
+    }
+}
+
+impl<B: Borrow<Buffer>> Multipart<B> {
+    pub fn new(body: B, boundary: &str) -> Self {
+        let mut delimiter = b"\r\n--".to_vec();
+        delimiter.extend_from_slice(boundary.as_bytes());
+        Self {
+            reader: BufferReader::new(body),
+            delimiter,
+            // 第一个分隔符前没有 CRLF，补上后与其余分隔符统一处理
+            pending: b"\r\n".to_vec(),
+            pos: 0,
+            scanned: 0,
+            next_delimiter: None,
+            state: State::Preamble,
+            parts: 0,
+            part_len: 0,
+            max_parts: DEFAULT_MAX_PARTS,
+            max_part_size: DEFAULT_MAX_PART_SIZE,
+            max_header_size: DEFAULT_MAX_HEADER_SIZE,
+        }
+    }
+
+    /// 单个 part 数据的最大字节数，超过时读取返回 413
+    pub fn max_part_size(mut self, bytes: u64) -> Self {
+        self.max_part_size = bytes;
+        self
+    }
+
+    /// 最多 part 数量，超过时返回 413
+    pub fn max_parts(mut self, count: usize) -> Self {
+        self.max_parts = count;
+        self
+    }
+
+    /// 单个 part 头部的最大字节数
+    pub fn max_header_size(mut self, bytes: usize) -> Self {
+        self.max_header_size = bytes;
+        self
+    }
+
+    /// 读取下一个 part（已读到结束分隔符时返回 `None`）
+    pub fn next_part(&mut self) -> VtxResult<Option<Part<'_, B>>> {
+        // 跳过前导内容与未读完的 part（不复制数据）
+        while matches!(self.state, State::Preamble | State::Body) {
+            self.next_body_span(usize::MAX)?;
+        }
+        if self.state == State::Done {
+            return Ok(None);
+        }
+
+        self.parts += 1;
+        if self.parts > self.max_parts {
+            return Err(too_large(format!(
+                "Multipart body has more than {} parts",
+                self.max_parts
+            )));
+        }
+
+        let headers = self.read_headers()?;
+        let (name, filename) = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("Content-Disposition"))
+            .map(|(_, v)| disposition(v))
+            .unwrap_or_default();
+
+        self.state = State::Body;
+        self.part_len = 0;
+        Ok(Some(Part {
+            multipart: self,
+            headers,
+            name,
+            filename,
+        }))
+    }
+
+    /// 读取 part 数据；读到分隔符时返回 0 并切换状态
+    fn read_body(&mut self, out: &mut [u8]) -> VtxResult<usize> {
+        match self.next_body_span(out.len())? {
+            Some(start) => {
+                let n = self.pos - start;
+                out[..n].copy_from_slice(&self.pending[start..self.pos]);
+                Ok(n)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// 消费至多 `max` 字节 part 数据，返回其在 `pending` 中的起始位置（数据止于 `pos`）；
+    /// 读到分隔符时返回 `None` 并切换状态
+    ///
+    /// 每个字节只扫描一次：已确认不含分隔符的范围记录在 `scanned`，
+    /// 找到的分隔符位置记录在 `next_delimiter`，分多次读取时不会重复查找。
+    fn next_body_span(&mut self, max: usize) -> VtxResult<Option<usize>> {
+        loop {
+            if self.pos < self.scanned {
+                let start = self.pos;
+                self.pos += (self.scanned - start).min(max);
+                return Ok(Some(start));
+            }
+            if self.next_delimiter == Some(self.pos) {
+                self.next_delimiter = None;
+                self.pos += self.delimiter.len();
+                self.scanned = self.pos;
+                self.after_delimiter()?;
+                return Ok(None);
+            }
+
+            // 此时 `pos` 之前的数据均已消费，从 `pos` 开始扫描（仅末尾不足一个分隔符的部分会被重复扫描）
+            let window = &self.pending[self.pos..];
+            match find(window, &self.delimiter) {
+                Some(idx) => {
+                    self.next_delimiter = Some(self.pos + idx);
+                    self.scanned = self.pos + idx;
+                }
+                // 末尾可能是分隔符的前缀，暂不返回
+                None if window.len() >= self.delimiter.len() => {
+                    self.scanned = self.pending.len() + 1 - self.delimiter.len();
+                }
+                None => {
+                    if !self.fill()? {
+                        return Err(malformed("body ended before the closing boundary"));
+                    }
+                }
+            }
+        }
+    }
+
+    /// 分隔符之后：`--` 表示结束，否则跳过空白后应为 CRLF
+    fn after_delimiter(&mut self) -> VtxResult<()> {
+        while self.pending.len() - self.pos < 2 {
+            if !self.fill()? {
+                return Err(malformed("body ended after a boundary"));
+            }
+        }
+        if &self.pending[self.pos..self.pos + 2] == b"--" {
+            self.state = State::Done;
+            return Ok(());
+        }
+        let line = self.read_line(self.max_header_size)?;
+        if line.iter().any(|b| !matches!(b, b' ' | b'\t')) {
+            return Err(malformed("unexpected data after a boundary"));
+        }
+        self.state = State::Headers;
+        Ok(())
+    }
+
+    fn read_headers(&mut self) -> VtxResult<Vec<(String, String)>> {
+        let mut headers: Vec<(String, String)> = Vec::new();
+        let mut budget = self.max_header_size;
+        loop {
+            let line = self.read_line(budget)?;
+            budget -= line.len();
+            if line.is_empty() {
+                return Ok(headers);
+            }
+            let line = String::from_utf8_lossy(&line);
+            // 以空白开头的行为上一个头部的续行
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                    continue;
+                }
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| malformed("invalid part header"))?;
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    /// 读取一行（不含 CRLF），超过 `limit` 字节时返回 413
+    fn read_line(&mut self, limit: usize) -> VtxResult<Vec<u8>> {
+        loop {
+            let window = &self.pending[self.pos..];
+            if let Some(idx) = find(window, b"\r\n") {
+                if idx > limit {
+                    break;
+                }
+                let line = window[..idx].to_vec();
+                self.pos += idx + 2;
+                return Ok(line);
+            }
+            if window.len() > limit {
+                break;
+            }
+            if !self.fill()? {
+                return Err(malformed("body ended inside part headers"));
+            }
+        }
+        Err(too_large(format!(
+            "Multipart part headers exceed {} bytes",
+            self.max_header_size
+        )))
+    }
+
+    /// 从 Buffer 读取更多数据，返回是否读到新数据
+    fn fill(&mut self) -> VtxResult<bool> {
+        self.pending.drain(..self.pos);
+        self.scanned = self.scanned.saturating_sub(self.pos);
+        self.next_delimiter = self.next_delimiter.map(|at| at - self.pos);
+        self.pos = 0;
+        let start = self.pending.len();
+        self.pending.resize(start + CHUNK, 0);
+        let n = self
+            .reader
+            .read(&mut self.pending[start..])
+            .map_err(|e| VtxError::Internal(format!("Buffer I/O failed: {}", e)))?;
+        self.pending.truncate(start + n);
+        Ok(n > 0)
+    }
+}
+
+/// multipart 中的单个 part（实现 `Read`，按需读取数据）
+pub struct Part<'m, B: Borrow<Buffer>> {
+    multipart: &'m mut Multipart<B>,
+    headers: Vec<(String, String)>,
+    name: Option<String>,
+    filename: Option<String>,
+}
+
+impl<B: Borrow<Buffer>> Part<'_, B> {
+    /// 表单字段名（`Content-Disposition` 的 `name`）
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// 上传文件名（`filename` / `filename*`，已去除路径）
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// part 的 `Content-Type`
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("Content-Type")
+    }
+
+    /// 获取 part 头部 (Case-insensitive)
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// 读取一段数据，part 结束时返回 0
+    pub fn read_chunk(&mut self, out: &mut [u8]) -> VtxResult<usize> {
+        let mp = &mut *self.multipart;
+        if mp.state != State::Body {
+            return Ok(0);
+        }
+        let n = mp.read_body(out)?;
+        mp.part_len += n as u64;
+        if mp.part_len > mp.max_part_size {
+            return Err(too_large(format!(
+                "Multipart part '{}' exceeds {} bytes",
+                self.name.as_deref().unwrap_or_default(),
+                mp.max_part_size
+            )));
+        }
+        Ok(n)
+    }
+
+    /// 读取剩余全部数据
+    pub fn read_all(&mut self) -> VtxResult<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut chunk = vec![0u8; CHUNK];
+        loop {
+            let n = self.read_chunk(&mut chunk)?;
+            if n == 0 {
+                return Ok(out);
+            }
+            out.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    /// 按 UTF-8 读取剩余全部数据
+    pub fn text(&mut self) -> VtxResult<String> {
+        String::from_utf8(self.read_all()?).map_err(|e| VtxError::SerializationError(e.to_string()))
+    }
+
+    /// 将剩余数据追加写入 Buffer，返回写入的字节数
+    pub fn copy_to(&mut self, dest: &Buffer) -> VtxResult<u64> {
+        let mut total = 0u64;
+        let mut chunk = vec![0u8; CHUNK];
+        loop {
+            let n = self.read_chunk(&mut chunk)?;
+            if n == 0 {
+                return Ok(total);
+            }
+            dest.write_all(&chunk[..n])?;
+            total += n as u64;
+        }
+    }
+}
+
+impl<B: Borrow<Buffer>> Read for Part<'_, B> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        self.read_chunk(out)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl Request {
+    /// 以 `multipart/form-data` 解析请求体（默认限制，详见 `Multipart`）
+    pub fn multipart(&self) -> VtxResult<Multipart<&Buffer>> {
+        Multipart::from_request(self)
+    }
+}
+
+/// 从 `Content-Type` 中提取 multipart boundary
+///
+/// ```rust
+/// use vtx_sdk::http::multipart::boundary;
+///
+/// assert_eq!(
+///     boundary("multipart/form-data; boundary=\"----abc\"").as_deref(),
+///     Some("----abc")
+/// );
+/// assert_eq!(boundary("application/json"), None);
+/// ```
+pub fn boundary(content_type: &str) -> Option<String> {
+    let (mime, params) = content_type.split_once(';')?;
+    if !mime.trim().to_ascii_lowercase().starts_with("multipart/") {
+        return None;
+    }
+    parse_params(params)
+        .into_iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("boundary"))
+        .map(|(_, v)| v)
+        .filter(|b| (1..=70).contains(&b.len()))
+}
+
+/// 解析 `Content-Disposition` 中的 `name` 与文件名（`filename*` 优先）
+fn disposition(value: &str) -> (Option<String>, Option<String>) {
+    let params = value.split_once(';').map(|(_, p)| p).unwrap_or_default();
+    let mut name = None;
+    let mut filename = None;
+    let mut filename_ext = None;
+
+    for (key, value) in parse_params(params) {
+        match key.to_ascii_lowercase().as_str() {
+            "name" => name = Some(value),
+            "filename" => filename = Some(value),
+            // RFC 5987：charset'language'percent-encoded
+            "filename*" => filename_ext = value.splitn(3, '\'').nth(2).map(percent_decode),
+            _ => {}
+        }
+    }
+
+    let filename = filename_ext
+        .or(filename)
+        .map(|f| f.rsplit(['/', '\\']).next().unwrap_or_default().to_string());
+    (name, filename)
+}
+
+/// 解析 `; key=value; key="quoted"` 形式的参数列表
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| *c == ';' || c.is_whitespace()).is_some() {}
+        let key: String =
+            std::iter::from_fn(|| chars.next_if(|c| *c != '=' && *c != ';')).collect();
+        if key.is_empty() && chars.peek().is_none() {
+            return params;
+        }
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            if chars.next_if_eq(&'"').is_some() {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => value.extend(chars.next()),
+                        c => value.push(c),
+                    }
+                }
+                while chars.next_if(|c| *c != ';').is_some() {}
+            } else {
+                value = std::iter::from_fn(|| chars.next_if(|c| *c != ';')).collect();
+                value = value.trim().to_string();
+            }
+        }
+        params.push((key.trim().to_string(), value));
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn malformed(reason: &str) -> VtxError {
+    VtxError::BadRequest(format!("Malformed multipart body: {}", reason))
+}
+
+fn too_large(detail: String) -> VtxError {
+    ProblemDetails::new(413, "payload_too_large")
+        .detail(detail)
+        .into()
+}
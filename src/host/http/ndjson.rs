@@ -0,0 +1,112 @@
+//! Streaming NDJSON responses.
+
+use super::{content_type, Response, ResponseBuilder};
+use crate::error::VtxResult;
+use crate::imports::stream_io::{self, Buffer};
+use crate::stream::NdjsonWriter;
+use serde::Serialize;
+
+/// 逐条写入的 NDJSON 响应（`application/x-ndjson`）
+///
+/// 记录分块写入宿主内存 Buffer，导出大量记录时无需在插件内存中拼接完整响应体。
+///
+/// ```rust,no_run
+/// use vtx_sdk::db;
+/// use vtx_sdk::http::ndjson::NdjsonResponse;
+/// use vtx_sdk::prelude::*;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Video {
+///     id: i64,
+///     title: String,
+/// }
+///
+/// fn export_videos() -> VtxResult<Response> {
+///     let mut resp = NdjsonResponse::new()
+///         .header("Content-Disposition", "attachment; filename=\"videos.ndjson\"");
+///     for page in db::query_pages::<Video>("SELECT id, title FROM videos", &[], 500) {
+///         resp.write_all(page?)?;
+///     }
+///     resp.finish()
+/// }
+/// ```
+pub struct NdjsonResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    writer: NdjsonWriter<Buffer>,
+}
+
+impl Default for NdjsonResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NdjsonResponse {
+    /// 200 OK，空响应体
+    pub fn new() -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            writer: NdjsonWriter::new(stream_io::create_memory_buffer(&[])),
+        }
+    }
+
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// 追加响应头
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// 写入一条记录
+    pub fn write<T: Serialize + ?Sized>(&mut self, record: &T) -> VtxResult<()> {
+        self.writer.write(record)
+    }
+
+    /// 依次写入全部记录
+    pub fn write_all<T, I>(&mut self, records: I) -> VtxResult<()>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        self.writer.write_all(records)
+    }
+
+    /// 已写入的记录数
+    pub fn count(&self) -> u64 {
+        self.writer.count()
+    }
+
+    /// 写出剩余数据并构造响应
+    pub fn finish(self) -> VtxResult<Response> {
+        let body = self.writer.finish()?;
+        let builder = self
+            .headers
+            .into_iter()
+            .fold(ResponseBuilder::new(self.status), |b, (k, v)| {
+                b.header(k, v)
+            });
+        Ok(builder
+            .content_type(content_type::NDJSON)
+            .body_buffer(body)
+            .build())
+    }
+}
+
+impl ResponseBuilder {
+    /// 构造 NDJSON 响应（200 OK），每条记录一行
+    pub fn ndjson<T, I>(records: I) -> VtxResult<Response>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let mut resp = NdjsonResponse::new();
+        resp.write_all(records)?;
+        resp.finish()
+    }
+}
@@ -0,0 +1,313 @@
+//! HTTP Range (partial content) support for file responses.
+
+use super::{Request, Response, ResponseBuilder};
+use crate::error::VtxError;
+use crate::imports::stream_io::Buffer;
+use crate::stream;
+
+/// 单次 206 响应默认的最大字节数（8 MiB）
+///
+/// 开放区间（如 `bytes=0-`）会被截断到该长度，播放器会按 `Content-Range` 继续请求后续分段。
+pub const DEFAULT_MAX_CHUNK: u64 = 8 * 1024 * 1024;
+
+/// 单次 206 响应允许的最大字节数上限（64 MiB）
+///
+/// 分段需完整读入插件内存后再写入响应体，`max_chunk` 为 0 或超过该值时按该值截断，
+/// 避免 `bytes=0-` 之类的请求把整个文件读入 Wasm 内存。
+pub const MAX_CHUNK_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// 闭区间字节范围 `[start, end]`（`end < start` 表示空区间）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    start: u64,
+    end: u64,
+}
+
+impl ByteRange {
+    pub const fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    /// 首字节偏移
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// 末字节偏移（含）
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// 范围长度（字节；空区间为 0）
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.end - self.start).saturating_add(1)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// `Content-Range` 头的值，如 `bytes 0-1023/4096`
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, total)
+    }
+}
+
+/// `Range` 请求头的解析结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// 无有效 Range（缺失、语法错误、非 bytes 单位或多区间），按完整内容响应
+    Full,
+    /// 单个可满足的区间
+    Partial(ByteRange),
+    /// 区间不可满足（416）
+    Unsatisfiable,
+}
+
+/// 解析 `Range` 请求头（RFC 9110 §14.2）
+///
+/// 仅支持单区间；多区间请求按 `Full` 处理（RFC 允许服务端忽略 Range）。
+///
+/// ```rust
+/// use vtx_sdk::http::range::{parse_range, ByteRange, RangeRequest};
+///
+/// assert_eq!(
+///     parse_range("bytes=0-99", 1000),
+///     RangeRequest::Partial(ByteRange::new(0, 99))
+/// );
+/// assert_eq!(
+///     parse_range("bytes=-100", 1000),
+///     RangeRequest::Partial(ByteRange::new(900, 999))
+/// );
+/// assert_eq!(parse_range("bytes=2000-", 1000), RangeRequest::Unsatisfiable);
+/// assert_eq!(parse_range("items=0-1", 1000), RangeRequest::Full);
+/// ```
+pub fn parse_range(header: &str, size: u64) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    let range = match (start.is_empty(), end.is_empty()) {
+        // bytes=-N：最后 N 个字节
+        (true, false) => {
+            let Some(suffix) = parse_pos(end) else {
+                return RangeRequest::Full;
+            };
+            if suffix == 0 || size == 0 {
+                return RangeRequest::Unsatisfiable;
+            }
+            ByteRange::new(size.saturating_sub(suffix), size - 1)
+        }
+        // bytes=N- 或 bytes=N-M
+        (false, _) => {
+            let Some(first) = parse_pos(start) else {
+                return RangeRequest::Full;
+            };
+            let last = if end.is_empty() {
+                u64::MAX
+            } else {
+                match parse_pos(end) {
+                    Some(last) if last >= first => last,
+                    _ => return RangeRequest::Full,
+                }
+            };
+            if first >= size {
+                return RangeRequest::Unsatisfiable;
+            }
+            ByteRange::new(first, last.min(size - 1))
+        }
+        (true, true) => return RangeRequest::Full,
+    };
+
+    RangeRequest::Partial(range)
+}
+
+/// 解析字节偏移（仅允许十进制数字；`u64::from_str` 会接受前导 `+`）
+fn parse_pos(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// 支持 Range / If-Range 的文件响应构造器
+///
+/// - 无 `Range` 或 Range 无效：200 + 完整文件流，附带 `Accept-Ranges: bytes`
+/// - 可满足的单区间：206 + `Content-Range`，仅读取所需分段
+/// - 不可满足：416 + `Content-Range: bytes */{size}`
+/// - `If-Range` 与当前 `ETag` / `Last-Modified` 不一致：忽略 Range，返回 200 完整内容
+///
+/// `ETag` 由调用方提供（通常为文件 UUID 与版本号的组合）；
+/// 若宿主无法提供文件大小（如 pipe），始终按完整内容响应。
+///
+/// 协议 3.6 的宿主不传递请求头，`Range` / `If-Range` 永远不会到达插件，
+/// 此时总是返回 200 完整内容（206 / 416 分支目前仅在 `testing` 的模拟宿主下可达）。
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::http::range::FileResponse;
+/// use vtx_sdk::prelude::*;
+///
+/// fn stream_video(req: &Request, uuid: &str) -> Response {
+///     FileResponse::new(uuid)
+///         .content_type("video/mp4")
+///         .etag(format!("\"{}\"", uuid))
+///         .respond(req)
+/// }
+/// ```
+pub struct FileResponse {
+    uuid: String,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    max_chunk: u64,
+}
+
+impl FileResponse {
+    pub fn new(uuid: impl Into<String>) -> Self {
+        Self {
+            uuid: uuid.into(),
+            content_type: None,
+            etag: None,
+            last_modified: None,
+            max_chunk: DEFAULT_MAX_CHUNK,
+        }
+    }
+
+    /// 设置 `Content-Type`
+    pub fn content_type(mut self, value: impl Into<String>) -> Self {
+        self.content_type = Some(value.into());
+        self
+    }
+
+    /// 设置实体标签（需包含引号，如 `"v1"` 或 `W/"v1"`）
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// 设置 `Last-Modified`（HTTP-date 格式）
+    pub fn last_modified(mut self, value: impl Into<String>) -> Self {
+        self.last_modified = Some(value.into());
+        self
+    }
+
+    /// 设置单次 206 响应的最大字节数（0 表示使用上限 `MAX_CHUNK_LIMIT`，超过上限时按上限处理）
+    pub fn max_chunk(mut self, bytes: u64) -> Self {
+        self.max_chunk = bytes;
+        self
+    }
+
+    /// 根据请求头生成响应
+    pub fn respond(self, req: &Request) -> Response {
+        let buffer = match stream::open_file(&self.uuid) {
+            Ok(buffer) => buffer,
+            Err(e) => return ResponseBuilder::error(e),
+        };
+
+        let size = buffer.size();
+        let range = match req.header("Range") {
+            Some(value) if size > 0 && self.if_range_matches(req) => parse_range(value, size),
+            _ => RangeRequest::Full,
+        };
+
+        match range {
+            RangeRequest::Full => self.headers(ResponseBuilder::new(200)).body_buffer(buffer),
+            RangeRequest::Partial(mut range) => {
+                let max_chunk = match self.max_chunk {
+                    0 => MAX_CHUNK_LIMIT,
+                    n => n.min(MAX_CHUNK_LIMIT),
+                };
+                if range.len() > max_chunk {
+                    range.end = range.start + max_chunk - 1;
+                }
+                // 宿主可能提前结束读取：按实际读到的字节数声明区间与长度
+                let data = read_range(&buffer, range);
+                if data.is_empty() {
+                    return ResponseBuilder::error(VtxError::Internal(format!(
+                        "Failed to read bytes {}-{} of file {}",
+                        range.start, range.end, self.uuid
+                    )));
+                }
+                range.end = range.start + data.len() as u64 - 1;
+                self.headers(ResponseBuilder::new(206))
+                    .header("Content-Range", range.content_range(size))
+                    .header("Content-Length", data.len().to_string())
+                    .body(data)
+            }
+            RangeRequest::Unsatisfiable => ResponseBuilder::new(416)
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Range", format!("bytes */{}", size)),
+        }
+        .build()
+    }
+
+    /// 校验 `If-Range`：缺失时视为匹配；仅允许强 ETag 比较或与 `Last-Modified` 完全一致
+    fn if_range_matches(&self, req: &Request) -> bool {
+        let Some(value) = req.header("If-Range").map(str::trim) else {
+            return true;
+        };
+
+        if value.starts_with('"') || value.starts_with("W/") {
+            return match &self.etag {
+                Some(etag) => !etag.starts_with("W/") && !value.starts_with("W/") && etag == value,
+                None => false,
+            };
+        }
+
+        self.last_modified.as_deref() == Some(value)
+    }
+
+    fn headers(&self, mut builder: ResponseBuilder) -> ResponseBuilder {
+        builder = builder.header("Accept-Ranges", "bytes");
+        if let Some(ct) = &self.content_type {
+            builder = builder.content_type(ct.clone());
+        }
+        if let Some(etag) = &self.etag {
+            builder = builder.header("ETag", etag.clone());
+        }
+        if let Some(lm) = &self.last_modified {
+            builder = builder.header("Last-Modified", lm.clone());
+        }
+        builder
+    }
+}
+
+/// 按偏移读取指定区间（宿主单次读取可能少于请求长度；宿主提前结束时返回已读到的部分）
+fn read_range(buffer: &Buffer, range: ByteRange) -> Vec<u8> {
+    const CHUNK: u64 = 64 * 1024;
+
+    let mut out = Vec::with_capacity(range.len() as usize);
+    let mut offset = range.start;
+    while offset <= range.end {
+        let to_read = std::cmp::min(CHUNK, range.end - offset + 1);
+        let chunk = buffer.read(offset, to_read);
+        if chunk.is_empty() {
+            break;
+        }
+        offset += chunk.len() as u64;
+        out.extend_from_slice(&chunk);
+    }
+    out
+}
+
+impl ResponseBuilder {
+    /// 构造支持 Range 请求的文件流响应（默认配置，详见 `FileResponse`）
+    pub fn file_ranged(req: &Request, uuid: &str) -> Response {
+        FileResponse::new(uuid).respond(req)
+    }
+}
@@ -1,10 +1,8 @@
 //! Plugin-side HTTP client helpers.
 
-use crate::bindings::vtx::api::{
-    http_client,
-    types::{HttpClientRequest, HttpClientResponse},
-};
+use crate::bindings::vtx::api::types::{HttpClientRequest, HttpClientResponse};
 use crate::error::{VtxError, VtxResult};
+use crate::imports::http_client;
 
 pub type Request = HttpClientRequest;
 pub type Response = HttpClientResponse;
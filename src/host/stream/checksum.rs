@@ -0,0 +1,99 @@
+//! Checksums computed while reading (`checksum` feature).
+//!
+//! 用于校验上传媒体的完整性：在读取数据的同时计算 CRC32 与 SHA-256，无需再遍历一次。
+//!
+//! ```rust,no_run
+//! use vtx_sdk::stream::{self, BufferExt};
+//!
+//! let upload = stream::open_file("uuid-of-upload")?;
+//! let sums = upload.checksums()?;
+//! sums.verify_sha256("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")?;
+//! # Ok::<(), vtx_sdk::error::VtxError>(())
+//! ```
+
+use crate::error::{ProblemDetails, VtxResult};
+use sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+/// 数据长度与校验值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checksums {
+    /// 已读取的字节数
+    pub len: u64,
+    /// CRC32（IEEE）
+    pub crc32: u32,
+    pub sha256: [u8; 32],
+}
+
+impl Checksums {
+    /// 小写十六进制的 SHA-256
+    pub fn sha256_hex(&self) -> String {
+        self.sha256.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// 8 位小写十六进制的 CRC32
+    pub fn crc32_hex(&self) -> String {
+        format!("{:08x}", self.crc32)
+    }
+
+    /// 校验 SHA-256（十六进制，大小写不敏感），不一致时返回 `422 checksum_mismatch`
+    pub fn verify_sha256(&self, expected: &str) -> VtxResult<()> {
+        let actual = self.sha256_hex();
+        if actual.eq_ignore_ascii_case(expected.trim()) {
+            return Ok(());
+        }
+        Err(ProblemDetails::new(422, "checksum_mismatch")
+            .detail("SHA-256 checksum does not match")
+            .extension("expected", expected.trim().to_ascii_lowercase())
+            .extension("actual", actual)
+            .into())
+    }
+}
+
+/// 读取时计算校验值的 `Read` 包装器
+pub struct ChecksumReader<R> {
+    inner: R,
+    len: u64,
+    crc32: crc32fast::Hasher,
+    sha256: Sha256,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            len: 0,
+            crc32: crc32fast::Hasher::new(),
+            sha256: Sha256::new(),
+        }
+    }
+
+    /// 截至目前已读取数据的校验值
+    pub fn checksums(&self) -> Checksums {
+        Checksums {
+            len: self.len,
+            crc32: self.crc32.clone().finalize(),
+            sha256: self.sha256.clone().finalize().into(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// 取回内部读取器与最终校验值
+    pub fn finish(self) -> (R, Checksums) {
+        let checksums = self.checksums();
+        (self.inner, checksums)
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(out)?;
+        self.len += n as u64;
+        self.crc32.update(&out[..n]);
+        self.sha256.update(&out[..n]);
+        Ok(n)
+    }
+}
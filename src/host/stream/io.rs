@@ -0,0 +1,273 @@
+//! `std::io` adapters over host buffers (`BufferReader` / `BufferWriter`).
+//!
+//! 宿主 `Buffer` 只提供 `size` / `read(offset, len)` / `write(data)`，本模块在此之上维护游标与缓冲区，
+//! 使 Buffer 可以直接交给 `zip`、`csv`、图像解码器等基于 `Read` / `Write` 的生态库使用。
+//!
+//! - file / memory（`size() > 0`）：按游标偏移读取，支持 `Seek`
+//! - pipe（`size() == 0`）：忽略偏移顺序读取，读到空数据即 EOF；仅支持不移动游标的 `Seek`
+//!   （如 `stream_position`）
+//!
+//! ```rust,no_run
+//! use std::io::{BufRead, Write};
+//! use vtx_sdk::stream::{self, BufferReader, BufferWriter};
+//!
+//! let input = stream::open_file("uuid-of-csv")?;
+//! let output = stream::memory_buffer([]);
+//! let mut writer = BufferWriter::new(&output);
+//! for line in BufferReader::new(&input).lines() {
+//!     let line = line?;
+//!     writeln!(writer, "{}", line.to_uppercase())?;
+//! }
+//! writer.flush()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::imports::stream_io::Buffer;
+use std::borrow::Borrow;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+/// 默认读写块大小
+const CHUNK: usize = 64 * 1024;
+
+/// 循环写入直到数据全部被接受或宿主接受 0 字节，返回已接受的字节数
+pub(super) fn write_looping(buffer: &Buffer, data: &[u8]) -> usize {
+    let mut written = 0;
+    while written < data.len() {
+        let accepted = usize::try_from(buffer.write(&data[written..]))
+            .unwrap_or(usize::MAX)
+            .min(data.len() - written);
+        if accepted == 0 {
+            break;
+        }
+        written += accepted;
+    }
+    written
+}
+
+/// 写入全部数据：宿主接受的字节数不足时继续写入剩余部分，接受 0 字节时报错
+fn write_fully(buffer: &Buffer, data: &[u8]) -> io::Result<()> {
+    if write_looping(buffer, data) < data.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::WriteZero,
+            "host buffer accepted no bytes",
+        ));
+    }
+    Ok(())
+}
+
+/// 带游标的 Buffer 读取器（实现 `Read` / `BufRead` / `Seek`）
+///
+/// 既可持有 Buffer（`BufferReader<Buffer>`），也可借用（`BufferReader<&Buffer>`）。
+pub struct BufferReader<B: Borrow<Buffer> = Buffer> {
+    buffer: B,
+    /// 是否支持按偏移读取（file / memory）
+    seekable: bool,
+    /// 下一个未消费字节在 Buffer 中的位置
+    pos: u64,
+    chunk: Vec<u8>,
+    chunk_pos: usize,
+    chunk_size: usize,
+    eof: bool,
+}
+
+impl<B: Borrow<Buffer>> BufferReader<B> {
+    pub fn new(buffer: B) -> Self {
+        Self::with_chunk_size(buffer, CHUNK)
+    }
+
+    /// 指定每次向宿主请求的字节数
+    pub fn with_chunk_size(buffer: B, chunk_size: usize) -> Self {
+        let seekable = buffer.borrow().size() > 0;
+        Self {
+            buffer,
+            seekable,
+            pos: 0,
+            chunk: Vec::new(),
+            chunk_pos: 0,
+            chunk_size: chunk_size.max(1),
+            eof: false,
+        }
+    }
+
+    /// 是否为可定位的 file / memory Buffer
+    pub fn is_seekable(&self) -> bool {
+        self.seekable
+    }
+
+    /// 已读取（消费）的字节位置
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn get_ref(&self) -> &Buffer {
+        self.buffer.borrow()
+    }
+
+    /// 取回底层 Buffer（未消费的缓冲数据将被丢弃）
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    fn buffered(&self) -> &[u8] {
+        &self.chunk[self.chunk_pos..]
+    }
+}
+
+impl<B: Borrow<Buffer>> Read for BufferReader<B> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<B: Borrow<Buffer>> BufRead for BufferReader<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.chunk_pos >= self.chunk.len() && !self.eof {
+            // pipe 模式下宿主忽略偏移
+            let offset = if self.seekable { self.pos } else { 0 };
+            self.chunk = self.buffer.borrow().read(offset, self.chunk_size as u64);
+            self.chunk_pos = 0;
+            // pipe 读到空数据即 EOF，之后不再请求宿主
+            self.eof = self.chunk.is_empty();
+        }
+        Ok(self.buffered())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.chunk.len() - self.chunk_pos);
+        self.chunk_pos += amt;
+        self.pos += amt as u64;
+    }
+}
+
+impl<B: Borrow<Buffer>> Seek for BufferReader<B> {
+    fn seek(&mut self, target: SeekFrom) -> io::Result<u64> {
+        let target = match target {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+            SeekFrom::End(delta) => {
+                if !self.seekable {
+                    return Err(unsupported());
+                }
+                self.buffer.borrow().size().checked_add_signed(delta)
+            }
+        }
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        if target == self.pos {
+            return Ok(self.pos);
+        }
+        if !self.seekable {
+            return Err(unsupported());
+        }
+
+        // 目标仍在当前缓冲块内时直接移动游标
+        let chunk_start = self.pos - self.chunk_pos as u64;
+        let chunk_end = chunk_start + self.chunk.len() as u64;
+        if (chunk_start..chunk_end).contains(&target) {
+            self.chunk_pos = (target - chunk_start) as usize;
+        } else {
+            self.chunk.clear();
+            self.chunk_pos = 0;
+        }
+        self.pos = target;
+        self.eof = false;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "pipe buffers do not support seeking",
+    )
+}
+
+/// 带缓冲的 Buffer 写入器（实现 `Write`，数据追加到 Buffer 末尾）
+///
+/// 缓冲区满或调用 `flush` 时写入宿主；`Drop` 时会尝试写出剩余数据但忽略错误，
+/// 需要确认写入成功时请显式调用 `flush` 或 `into_inner`。
+pub struct BufferWriter<B: Borrow<Buffer> = Buffer> {
+    buffer: Option<B>,
+    pending: Vec<u8>,
+    capacity: usize,
+}
+
+impl<B: Borrow<Buffer>> BufferWriter<B> {
+    pub fn new(buffer: B) -> Self {
+        Self::with_capacity(buffer, CHUNK)
+    }
+
+    /// 指定缓冲区大小（`0` 表示每次 `write` 直接写入宿主）
+    pub fn with_capacity(buffer: B, capacity: usize) -> Self {
+        Self {
+            buffer: Some(buffer),
+            pending: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn get_ref(&self) -> &Buffer {
+        self.inner().borrow()
+    }
+
+    /// 写出缓冲数据并取回底层 Buffer
+    pub fn into_inner(mut self) -> io::Result<B> {
+        self.flush_pending()?;
+        Ok(self.buffer.take().expect("buffer present until into_inner"))
+    }
+
+    fn inner(&self) -> &B {
+        self.buffer
+            .as_ref()
+            .expect("buffer present until into_inner")
+    }
+
+    fn flush_pending(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let result = write_fully(self.inner().borrow(), &self.pending);
+            // 失败时宿主可能已接受部分数据，丢弃缓冲以免重复写入
+            self.pending.clear();
+            result?;
+        }
+        Ok(())
+    }
+}
+
+impl<B: Borrow<Buffer>> Write for BufferWriter<B> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.pending.len() + data.len() > self.capacity {
+            self.flush_pending()?;
+        }
+        if data.len() >= self.capacity {
+            write_fully(self.inner().borrow(), data)?;
+        } else {
+            self.pending.extend_from_slice(data);
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_pending()
+    }
+}
+
+impl<B: Borrow<Buffer>> Drop for BufferWriter<B> {
+    fn drop(&mut self) {
+        if self.buffer.is_some() {
+            let _ = self.flush_pending();
+        }
+    }
+}
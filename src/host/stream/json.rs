@@ -0,0 +1,171 @@
+//! Streaming JSON / NDJSON over host buffers.
+//!
+//! 基于 `BufferReader` / `BufferWriter` 分块读写，数据不会整体复制到 Wasm 线性内存：
+//!
+//! - `BufferExt::read_json` / `write_json`：单个 JSON 值
+//! - `NdjsonReader`（`BufferExt::read_ndjson`）：逐行反序列化 NDJSON（每行一个 JSON 值）
+//! - `NdjsonWriter`：逐条序列化并追加换行
+//!
+//! ```rust,no_run
+//! use vtx_sdk::stream::{self, BufferExt, NdjsonWriter};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Record {
+//!     id: i64,
+//! }
+//!
+//! let input = stream::open_file("uuid-of-export")?;
+//! let output = stream::memory_buffer([]);
+//! let mut writer = NdjsonWriter::new(&output);
+//! for record in input.read_ndjson::<Record>() {
+//!     let record = record?;
+//!     writer.write(&Record { id: record.id + 1 })?;
+//! }
+//! writer.finish()?;
+//! # Ok::<(), vtx_sdk::error::VtxError>(())
+//! ```
+
+use super::{io_error, BufferReader, BufferWriter};
+use crate::error::{VtxError, VtxResult};
+use crate::imports::stream_io::Buffer;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::io::{BufRead, Write};
+use std::marker::PhantomData;
+
+/// 将 `serde_json` 错误映射为 `VtxError`（底层 I/O 错误不视为数据格式错误）
+fn json_error(err: serde_json::Error) -> VtxError {
+    if err.is_io() {
+        io_error(err.into())
+    } else {
+        VtxError::SerializationError(err.to_string())
+    }
+}
+
+/// 从 Buffer 流式反序列化单个 JSON 值
+pub(super) fn read_json<T: DeserializeOwned>(buffer: &Buffer) -> VtxResult<T> {
+    serde_json::from_reader(BufferReader::new(buffer)).map_err(json_error)
+}
+
+/// 将单个 JSON 值流式序列化并追加写入 Buffer
+pub(super) fn write_json<T: Serialize + ?Sized>(buffer: &Buffer, value: &T) -> VtxResult<()> {
+    let mut writer = BufferWriter::new(buffer);
+    serde_json::to_writer(&mut writer, value).map_err(json_error)?;
+    writer.flush().map_err(io_error)
+}
+
+/// NDJSON 逐行读取器（`Iterator<Item = VtxResult<T>>`）
+///
+/// - 空行被跳过，行尾的 `\r\n` 视同 `\n`
+/// - 某一行解析失败时返回带行号的 `SerializationError`，之后仍可继续读取下一行
+/// - Buffer 读取失败时返回错误并结束迭代
+pub struct NdjsonReader<B: Borrow<Buffer>, T> {
+    reader: BufferReader<B>,
+    line: Vec<u8>,
+    line_no: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<B: Borrow<Buffer>, T> NdjsonReader<B, T> {
+    pub fn new(buffer: B) -> Self {
+        Self::from_reader(BufferReader::new(buffer))
+    }
+
+    /// 从已有读取器继续读取（如跳过文件头之后）
+    pub fn from_reader(reader: BufferReader<B>) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            line_no: 0,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// 最近读取的行号（从 1 开始）
+    pub fn line_number(&self) -> u64 {
+        self.line_no
+    }
+
+    pub fn into_inner(self) -> BufferReader<B> {
+        self.reader
+    }
+}
+
+impl<B: Borrow<Buffer>, T: DeserializeOwned> Iterator for NdjsonReader<B, T> {
+    type Item = VtxResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line_no += 1;
+                    if self.line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    return Some(serde_json::from_slice(&self.line).map_err(|e| {
+                        VtxError::SerializationError(format!("line {}: {}", self.line_no, e))
+                    }));
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(io_error(err)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// NDJSON 逐条写入器（每条记录一行，带缓冲追加到 Buffer）
+///
+/// 结束时请调用 `finish` 写出缓冲数据并确认写入成功。
+pub struct NdjsonWriter<B: Borrow<Buffer>> {
+    writer: BufferWriter<B>,
+    count: u64,
+}
+
+impl<B: Borrow<Buffer>> NdjsonWriter<B> {
+    pub fn new(buffer: B) -> Self {
+        Self::from_writer(BufferWriter::new(buffer))
+    }
+
+    pub fn from_writer(writer: BufferWriter<B>) -> Self {
+        Self { writer, count: 0 }
+    }
+
+    /// 写入一条记录
+    pub fn write<T: Serialize + ?Sized>(&mut self, record: &T) -> VtxResult<()> {
+        // 先序列化为单行，避免失败时在 Buffer 中留下半条记录
+        let mut line = serde_json::to_vec(record).map_err(json_error)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).map_err(io_error)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// 依次写入全部记录
+    pub fn write_all<T, I>(&mut self, records: I) -> VtxResult<()>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        records
+            .into_iter()
+            .try_for_each(|record| self.write(&record))
+    }
+
+    /// 已写入的记录数
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 写出缓冲数据并取回底层 Buffer
+    pub fn finish(self) -> VtxResult<B> {
+        self.writer.into_inner().map_err(io_error)
+    }
+}
@@ -0,0 +1,213 @@
+//! Host-side stream I/O helpers (Buffer).
+
+use crate::error::{ProblemDetails, VtxError, VtxResult};
+use crate::imports::stream_io::{self, Buffer};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::Read;
+
+#[cfg(feature = "checksum")]
+mod checksum;
+mod io;
+mod json;
+
+#[cfg(feature = "checksum")]
+pub use checksum::{ChecksumReader, Checksums};
+pub use io::{BufferReader, BufferWriter};
+pub use json::{NdjsonReader, NdjsonWriter};
+
+pub type StreamBuffer = Buffer;
+
+/// 通过宿主的 UUID 打开文件并返回 `Buffer` 资源句柄。
+pub fn open_file(uuid: &str) -> VtxResult<Buffer> {
+    stream_io::open_file(uuid).map_err(VtxError::from_host_message)
+}
+
+/// 创建一个内存 Buffer（通常用于构造 `HttpResponse.body`）。
+pub fn memory_buffer(data: impl AsRef<[u8]>) -> Buffer {
+    stream_io::create_memory_buffer(data.as_ref())
+}
+
+/// `read_all` / `try_read_all` 的默认读取上限（64MB）
+pub const MAX_READ_ALL: usize = 64 * 1024 * 1024;
+
+/// `Buffer` 资源的便捷扩展方法。
+pub trait BufferExt {
+    /// 读取整个 Buffer。
+    ///
+    /// - 对于 file/memory：使用 `size()` 做分块读取。
+    /// - 对于 pipe：会持续读取直到 EOF（返回空数组）或到达 `MAX_READ_ALL`。
+    ///
+    /// 超过上限的部分会被静默截断；需要区分截断与 EOF 时请使用 `try_read_all` / `read_all_limited`。
+    fn read_all(&self) -> Vec<u8>;
+
+    /// 读取整个 Buffer，超过 `MAX_READ_ALL` 或 file/memory 提前结束时返回错误。
+    fn try_read_all(&self) -> VtxResult<Vec<u8>>;
+
+    /// 读取整个 Buffer，最多 `max` 字节。
+    ///
+    /// - 超过 `max` 时返回 `413 payload_too_large`（file/memory 在读取前即按 `size()` 判断）
+    /// - file/memory 读到的数据少于 `size()` 时返回 `internal` 错误
+    fn read_all_limited(&self, max: usize) -> VtxResult<Vec<u8>>;
+
+    /// 按 UTF-8 读取整个 Buffer（超过 `MAX_READ_ALL` 时返回错误，同 `try_read_all`）。
+    fn read_to_string(&self) -> VtxResult<String>;
+
+    /// 将 Buffer 中的 JSON 反序列化为目标类型（分块流式解析，不先读入完整字符串）。
+    fn read_json<T: DeserializeOwned>(&self) -> VtxResult<T>;
+
+    /// 将值序列化为 JSON 并追加写入（分块写入，不先生成完整字节数组）。
+    fn write_json<T: Serialize + ?Sized>(&self, value: &T) -> VtxResult<()>;
+
+    /// 按 NDJSON（每行一个 JSON 值）逐条读取。
+    fn read_ndjson<T: DeserializeOwned>(&self) -> NdjsonReader<&Buffer, T>;
+
+    /// 向 Buffer 追加写入（对文件：append；对 pipe：写入 stdin；对 memory：append）。
+    ///
+    /// 宿主单次只接受部分数据时继续写入剩余部分；宿主不再接受数据（未能写入全部数据）时返回错误。
+    fn write_all(&self, data: impl AsRef<[u8]>) -> VtxResult<()>;
+
+    /// 以 `std::io::Read` / `BufRead` / `Seek` 方式读取（游标从 0 开始）。
+    fn reader(&self) -> BufferReader<&Buffer>;
+
+    /// 以 `std::io::Write` 方式追加写入（带缓冲，结束时需 `flush`）。
+    fn writer(&self) -> BufferWriter<&Buffer>;
+
+    /// 流式计算整个 Buffer 的长度、CRC32 与 SHA-256（不在内存中保留数据）。
+    #[cfg(feature = "checksum")]
+    fn checksums(&self) -> VtxResult<Checksums>;
+
+    /// 同 `read_all_limited`，并同时计算校验值。
+    #[cfg(feature = "checksum")]
+    fn read_all_with_checksums(&self, max: usize) -> VtxResult<(Vec<u8>, Checksums)>;
+}
+
+impl BufferExt for Buffer {
+    fn read_all(&self) -> Vec<u8> {
+        const CHUNK: u64 = 64 * 1024;
+
+        let mut out = Vec::new();
+
+        let total = self.size();
+        if total > 0 {
+            let mut offset = 0u64;
+            while offset < total && out.len() < MAX_READ_ALL {
+                let to_read = std::cmp::min(CHUNK, total - offset);
+                let chunk = self.read(offset, to_read);
+                if chunk.is_empty() {
+                    break;
+                }
+                out.extend_from_slice(&chunk);
+                offset += chunk.len() as u64;
+            }
+            return out;
+        }
+
+        // Pipe 模式：不知道 size，读到空为止（EOF）。
+        while out.len() < MAX_READ_ALL {
+            let chunk = self.read(0, CHUNK);
+            if chunk.is_empty() {
+                break;
+            }
+            out.extend_from_slice(&chunk);
+        }
+
+        out
+    }
+
+    fn try_read_all(&self) -> VtxResult<Vec<u8>> {
+        self.read_all_limited(MAX_READ_ALL)
+    }
+
+    fn read_all_limited(&self, max: usize) -> VtxResult<Vec<u8>> {
+        read_limited(self.reader(), self.size(), max)
+    }
+
+    fn read_to_string(&self) -> VtxResult<String> {
+        let bytes = self.try_read_all()?;
+        String::from_utf8(bytes).map_err(|e| VtxError::SerializationError(e.to_string()))
+    }
+
+    fn read_json<T: DeserializeOwned>(&self) -> VtxResult<T> {
+        json::read_json(self)
+    }
+
+    fn write_json<T: Serialize + ?Sized>(&self, value: &T) -> VtxResult<()> {
+        json::write_json(self, value)
+    }
+
+    fn read_ndjson<T: DeserializeOwned>(&self) -> NdjsonReader<&Buffer, T> {
+        NdjsonReader::new(self)
+    }
+
+    fn write_all(&self, data: impl AsRef<[u8]>) -> VtxResult<()> {
+        let data = data.as_ref();
+        let written = io::write_looping(self, data);
+        if written < data.len() {
+            return Err(VtxError::Internal(format!(
+                "Buffer accepted only {} of {} bytes",
+                written,
+                data.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn reader(&self) -> BufferReader<&Buffer> {
+        BufferReader::new(self)
+    }
+
+    fn writer(&self) -> BufferWriter<&Buffer> {
+        BufferWriter::new(self)
+    }
+
+    #[cfg(feature = "checksum")]
+    fn checksums(&self) -> VtxResult<Checksums> {
+        let mut reader = ChecksumReader::new(self.reader());
+        std::io::copy(&mut reader, &mut std::io::sink()).map_err(io_error)?;
+        Ok(reader.checksums())
+    }
+
+    #[cfg(feature = "checksum")]
+    fn read_all_with_checksums(&self, max: usize) -> VtxResult<(Vec<u8>, Checksums)> {
+        let mut reader = ChecksumReader::new(self.reader());
+        let data = read_limited(&mut reader, self.size(), max)?;
+        Ok((data, reader.checksums()))
+    }
+}
+
+/// 从 `reader` 读取最多 `max` 字节；`size > 0` 时要求恰好读到 `size` 字节
+fn read_limited(reader: impl Read, size: u64, max: usize) -> VtxResult<Vec<u8>> {
+    if size > max as u64 {
+        return Err(too_large(max));
+    }
+
+    // 多读 1 字节用于判断 pipe 是否超过上限
+    let mut out = Vec::with_capacity(size as usize);
+    reader
+        .take(max as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(io_error)?;
+
+    if out.len() > max {
+        return Err(too_large(max));
+    }
+    if (out.len() as u64) < size {
+        return Err(VtxError::Internal(format!(
+            "Buffer ended after {} of {} bytes",
+            out.len(),
+            size
+        )));
+    }
+    Ok(out)
+}
+
+fn too_large(max: usize) -> VtxError {
+    ProblemDetails::new(413, "payload_too_large")
+        .detail(format!("Buffer exceeds the limit of {} bytes", max))
+        .into()
+}
+
+fn io_error(err: std::io::Error) -> VtxError {
+    VtxError::Internal(format!("Buffer I/O failed: {}", err))
+}
@@ -0,0 +1,252 @@
+//! Plugin key-value store on top of `db`.
+//!
+//! 适用于设置项与少量状态数据，无需为此单独建表与编写迁移：
+//!
+//! - 值以 JSON 文本存储，读取时反序列化为目标类型
+//! - 每个键带有版本号，`compare_and_swap` 基于版本号原子更新
+//! - 可选 TTL：过期的键读取时视为不存在，`purge_expired` 批量清理
+//!
+//! 数据保存在 SDK 管理的 `_vtx_kv` 表中，建议通过 `kv::migration` 将建表语句加入插件迁移。
+//! 表尚不存在时：读取（`get`、`list_prefix` 等）按空存储处理且不执行任何 DDL，
+//! 因此在只读数据库上也可用；写入会先执行 `CREATE TABLE IF NOT EXISTS` 再重试一次。
+//! 所有写操作（包括 `delete`）均经 `db::execute` 执行，受宿主安全策略约束。
+//! 过期时间使用数据库时钟（`strftime('%s', 'now')`）计算，与插件运行环境无关。
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use vtx_sdk::kv;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize, Default)]
+//! struct Settings {
+//!     quality: String,
+//! }
+//!
+//! let mut settings: Settings = kv::get("settings")?.unwrap_or_default();
+//! settings.quality = "1080p".into();
+//! kv::set("settings", &settings)?;
+//!
+//! kv::set_with_ttl("session:abc", &"u1", Duration::from_secs(3600))?;
+//! let sessions: Vec<(String, String)> = kv::list_prefix("session:")?;
+//! # Ok::<(), vtx_sdk::error::VtxError>(())
+//! ```
+
+use crate::db::{self, DbErrorKind, Row};
+use crate::error::{VtxError, VtxResult};
+use crate::migrations::Migration;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+
+/// 存储表名
+pub const TABLE: &str = "_vtx_kv";
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS _vtx_kv (\
+     key TEXT PRIMARY KEY, \
+     value TEXT NOT NULL, \
+     version INTEGER NOT NULL DEFAULT 1, \
+     expires_at INTEGER)";
+
+/// 当前 Unix 秒（数据库时钟）
+const NOW: &str = "CAST(strftime('%s', 'now') AS INTEGER)";
+
+/// 存储表的迁移（`version` 为其在插件迁移序列中的版本号）
+///
+/// ```rust
+/// use vtx_sdk::kv;
+/// use vtx_sdk::migrations::Migration;
+///
+/// static MIGRATIONS: &[Migration] = &[
+///     Migration::new(1, "create_videos", "CREATE TABLE videos (id TEXT PRIMARY KEY)"),
+///     kv::migration(2),
+/// ];
+/// # assert!(vtx_sdk::migrations::validate(MIGRATIONS).is_ok());
+/// ```
+pub const fn migration(version: u32) -> Migration {
+    Migration::new(version, "create_vtx_kv", SCHEMA).with_down("DROP TABLE IF EXISTS _vtx_kv")
+}
+
+/// 错误是否由存储表不存在引起
+fn is_missing_table(result: &VtxResult<impl Sized>) -> bool {
+    matches!(
+        result,
+        Err(VtxError::Db(err)) if err.kind == DbErrorKind::Syntax && err.message.contains(TABLE)
+    )
+}
+
+/// 执行写操作；表尚未创建时建表后重试一次
+fn with_table<T>(op: impl Fn() -> VtxResult<T>) -> VtxResult<T> {
+    let result = op();
+    if is_missing_table(&result) {
+        db::execute(SCHEMA, &[])?;
+        return op();
+    }
+    result
+}
+
+/// 执行读操作（或对不存在的键无影响的写操作）；表尚未创建时视为空存储，不执行 DDL
+fn or_empty<T: Default>(op: impl Fn() -> VtxResult<T>) -> VtxResult<T> {
+    let result = op();
+    if is_missing_table(&result) {
+        return Ok(T::default());
+    }
+    result
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> VtxResult<String> {
+    serde_json::to_string(value).map_err(|e| VtxError::SerializationError(e.to_string()))
+}
+
+fn ttl_secs(ttl: Duration) -> VtxResult<i64> {
+    // 不足 1 秒的部分向上取整，避免 TTL 为 0 时立即过期
+    let secs = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+    i64::try_from(secs)
+        .map_err(|_| VtxError::BadRequest(format!("TTL of {} seconds is too large", secs)))
+}
+
+/// 带版本号的值（用于 `compare_and_swap`）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versioned<T> {
+    pub value: T,
+    pub version: i64,
+}
+
+/// 读取键（不存在或已过期时返回 `None`）
+pub fn get<T: DeserializeOwned>(key: &str) -> VtxResult<Option<T>> {
+    Ok(get_versioned(key)?.map(|v| v.value))
+}
+
+/// 读取键及其版本号
+pub fn get_versioned<T: DeserializeOwned>(key: &str) -> VtxResult<Option<Versioned<T>>> {
+    let sql = format!(
+        "SELECT value, version FROM {} WHERE key = ? AND (expires_at IS NULL OR expires_at > {})",
+        TABLE, NOW
+    );
+    let row: Option<Row> = or_empty(|| Ok(db::query_as::<Row>(&sql, &[&key])?.into_iter().next()))?;
+
+    row.map(|row| {
+        Ok(Versioned {
+            value: row.get_json("value")?,
+            version: row.get("version")?,
+        })
+    })
+    .transpose()
+}
+
+/// 写入键（覆盖已有值并清除 TTL）
+pub fn set<T: Serialize + ?Sized>(key: &str, value: &T) -> VtxResult<()> {
+    upsert(key, value, None)
+}
+
+/// 写入键，并在 `ttl` 后过期
+pub fn set_with_ttl<T: Serialize + ?Sized>(key: &str, value: &T, ttl: Duration) -> VtxResult<()> {
+    upsert(key, value, Some(ttl_secs(ttl)?))
+}
+
+fn upsert<T: Serialize + ?Sized>(key: &str, value: &T, ttl: Option<i64>) -> VtxResult<()> {
+    let json = to_json(value)?;
+    // NOW + NULL 为 NULL，即不过期
+    let sql = format!(
+        "INSERT INTO {table} (key, value, version, expires_at) VALUES (?, ?, 1, {now} + ?) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, \
+         version = {table}.version + 1, expires_at = excluded.expires_at",
+        table = TABLE,
+        now = NOW
+    );
+    with_table(|| db::execute(&sql, &[&key, &json, &ttl]))?;
+    Ok(())
+}
+
+/// 删除键，返回键是否存在（已过期的键视为不存在）
+pub fn delete(key: &str) -> VtxResult<bool> {
+    // 写操作只走 `db::execute`：先删除未过期的键得到返回值，再清理同名的过期键
+    let live = format!(
+        "DELETE FROM {} WHERE key = ? AND (expires_at IS NULL OR expires_at > {})",
+        TABLE, NOW
+    );
+    let expired = format!(
+        "DELETE FROM {} WHERE key = ? AND expires_at IS NOT NULL AND expires_at <= {}",
+        TABLE, NOW
+    );
+    let deleted = or_empty(|| db::execute(&live, &[&key]))?;
+    or_empty(|| db::execute(&expired, &[&key]))?;
+    Ok(deleted > 0)
+}
+
+/// 列出以 `prefix` 开头的全部未过期键值（按键排序）
+pub fn list_prefix<T: DeserializeOwned>(prefix: &str) -> VtxResult<Vec<(String, T)>> {
+    let sql = format!(
+        "SELECT key, value FROM {} WHERE substr(key, 1, ?) = ? \
+         AND (expires_at IS NULL OR expires_at > {}) ORDER BY key",
+        TABLE, NOW
+    );
+    let len = prefix.chars().count() as i64;
+    let rows: Vec<Row> = or_empty(|| db::query_as(&sql, &[&len, &prefix]))?;
+
+    rows.iter()
+        .map(|row| Ok((row.get("key")?, row.get_json("value")?)))
+        .collect()
+}
+
+/// 比较并交换：当前值与 `expected` 相等时写入 `new`，返回是否写入成功
+///
+/// `expected` 为 `None` 表示仅在键不存在（或已过期）时写入。
+/// 比较按 JSON 结构进行（对象成员顺序无关），写入以版本号为条件，
+/// 读取与写入之间被其他实例修改时返回 `false`。成功写入会清除 TTL。
+pub fn compare_and_swap<T: Serialize + ?Sized>(
+    key: &str,
+    expected: Option<&T>,
+    new: &T,
+) -> VtxResult<bool> {
+    let current: Option<Versioned<serde_json::Value>> = get_versioned(key)?;
+    let json = to_json(new)?;
+
+    match (expected, current) {
+        (None, None) => {
+            let sql = format!(
+                "INSERT INTO {table} (key, value, version) VALUES (?, ?, 1) \
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, \
+                 version = {table}.version + 1, expires_at = NULL \
+                 WHERE {table}.expires_at IS NOT NULL AND {table}.expires_at <= {now}",
+                table = TABLE,
+                now = NOW
+            );
+            Ok(with_table(|| db::execute(&sql, &[&key, &json]))? == 1)
+        }
+        (Some(expected), Some(current)) => {
+            let expected = serde_json::to_value(expected)
+                .map_err(|e| VtxError::SerializationError(e.to_string()))?;
+            if expected != current.value {
+                return Ok(false);
+            }
+            swap_version(key, &json, current.version)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// 按版本号条件写入：仅当当前版本为 `version` 时更新，返回是否写入成功
+pub fn set_if_version<T: Serialize + ?Sized>(
+    key: &str,
+    value: &T,
+    version: i64,
+) -> VtxResult<bool> {
+    swap_version(key, &to_json(value)?, version)
+}
+
+fn swap_version(key: &str, json: &str, version: i64) -> VtxResult<bool> {
+    let sql = format!(
+        "UPDATE {} SET value = ?, version = version + 1, expires_at = NULL \
+         WHERE key = ? AND version = ? AND (expires_at IS NULL OR expires_at > {})",
+        TABLE, NOW
+    );
+    Ok(or_empty(|| db::execute(&sql, &[&json, &key, &version]))? == 1)
+}
+
+/// 删除全部已过期的键，返回删除数量
+pub fn purge_expired() -> VtxResult<u64> {
+    let sql = format!(
+        "DELETE FROM {} WHERE expires_at IS NOT NULL AND expires_at <= {}",
+        TABLE, NOW
+    );
+    or_empty(|| db::execute(&sql, &[]))
+}
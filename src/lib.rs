@@ -7,6 +7,18 @@ pub mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 }
 
+/// 宿主导入接口（内部使用）
+///
+/// 默认指向 `wit-bindgen` 生成的宿主导入；启用 `testing` 特性的原生构建中
+/// 由 `build.rs` 将这些接口重映射到 `testing::imports` 下的 Mock 实现。
+pub(crate) mod imports {
+    #[cfg(not(vtx_mock_host))]
+    pub use crate::bindings::vtx::api::{context, event_bus, ffmpeg, http_client, sql, stream_io};
+
+    #[cfg(vtx_mock_host)]
+    pub use crate::testing::imports::{context, event_bus, ffmpeg, http_client, sql, stream_io};
+}
+
 // =====================
 // 模块定义
 // =====================
@@ -26,6 +38,21 @@ pub mod prelude;
 /// 更低样板的插件导出适配
 pub mod plugin;
 
+/// 原生单元测试工具：进程内 Mock 宿主（需启用 `testing` 特性，仅限非 Wasm 目标）
+#[cfg(vtx_mock_host)]
+pub mod testing;
+
+/// 声明式 HTTP 路由（方法 + 路径模式分发）
+pub mod router;
+
+/// 版本化数据库迁移（`VtxPlugin::get_migrations`）
+pub mod migrations;
+
+/// 插件键值存储（基于 `db`，支持 CAS 与 TTL）
+pub mod kv;
+
+mod hash;
+
 // =====================
 // 类型导出（供外部模块使用）
 // =====================
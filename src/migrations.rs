@@ -0,0 +1,256 @@
+//! Versioned database migrations.
+//!
+//! 宿主通过 `get_migrations` 获取按顺序执行的 SQL 脚本列表。本模块在此之上提供：
+//!
+//! - `Migration`：版本号、名称、`up` / `down` 脚本与内容校验和
+//! - `include_migrations!`（`derive` 特性）：编译期嵌入目录中的 `.sql` 文件并按版本排序
+//! - `to_host_scripts`：校验并转换为 `get_migrations` 的返回值，每个脚本末尾追加执行记录
+//! - `verify_applied`：比对已执行迁移的校验和，发现「已发布后被修改」的迁移
+//!
+//! 执行记录保存在 `_vtx_migrations` 表中（由转换后的脚本自动创建）。
+//!
+//! ```rust
+//! use vtx_sdk::migrations::{self, Migration};
+//!
+//! static MIGRATIONS: &[Migration] = &[
+//!     Migration::new(1, "create_videos", "CREATE TABLE videos (id TEXT PRIMARY KEY, title TEXT)")
+//!         .with_down("DROP TABLE videos"),
+//!     Migration::new(2, "add_views", "ALTER TABLE videos ADD COLUMN views INTEGER DEFAULT 0"),
+//! ];
+//!
+//! assert!(migrations::validate(MIGRATIONS).is_ok());
+//! assert_eq!(migrations::to_host_scripts(MIGRATIONS).len(), 2);
+//! ```
+
+use crate::error::{VtxError, VtxResult};
+use crate::hash::fnv1a_64;
+use serde::Deserialize;
+
+/// `include_migrations!("migrations/")`：编译期嵌入迁移目录（需启用 `derive` 特性）
+///
+/// 目录相对于调用方 crate 根目录（`CARGO_MANIFEST_DIR`），文件命名规则：
+///
+/// - `0001_create_videos.sql` 或 `0001_create_videos.up.sql`：`up` 脚本
+/// - `0001_create_videos.down.sql`：对应的 `down` 脚本（可选）
+/// - 版本号前可带 `V` 前缀，版本号与名称之间可使用 `_` 或 `__`
+///
+/// 展开为按版本升序排列的 `&'static [Migration]`；版本号重复、缺少 `up` 脚本
+/// 或文件名不符合规则时产生编译错误。
+///
+/// 已嵌入文件的修改会经 `include_str!` 触发重新编译，但稳定版 Rust 无法让过程宏追踪目录本身，
+/// **新增或删除** 迁移文件不会触发重新编译。请在调用方 crate 的 `build.rs` 中声明目录依赖：
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=migrations");
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use vtx_sdk_derive::include_migrations;
+
+/// 迁移执行记录表
+pub const HISTORY_TABLE: &str = "_vtx_migrations";
+
+/// 单个版本化迁移
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// 版本号（严格递增）
+    pub version: u32,
+    /// 名称（仅用于展示与记录）
+    pub name: &'static str,
+    /// 升级脚本（可包含多条语句）
+    pub up: &'static str,
+    /// 回滚脚本（可选，可包含多条语句）
+    pub down: Option<&'static str>,
+}
+
+impl Migration {
+    pub const fn new(version: u32, name: &'static str, up: &'static str) -> Self {
+        Self {
+            version,
+            name,
+            up,
+            down: None,
+        }
+    }
+
+    pub const fn with_down(mut self, down: &'static str) -> Self {
+        self.down = Some(down);
+        self
+    }
+
+    /// `up` 脚本的校验和（忽略换行风格与行尾空白）
+    pub fn checksum(&self) -> String {
+        let normalized: Vec<&str> = self.up.lines().map(str::trim_end).collect();
+        let normalized = normalized.join("\n");
+        format!("{:016x}", fnv1a_64(normalized.trim().as_bytes()))
+    }
+}
+
+/// 校验迁移列表：版本号唯一且严格递增、`up` 脚本非空
+pub fn validate(migrations: &[Migration]) -> VtxResult<()> {
+    for (i, m) in migrations.iter().enumerate() {
+        if m.up.trim().is_empty() {
+            return Err(VtxError::Internal(format!(
+                "Migration {} ({}) has an empty up script",
+                m.version, m.name
+            )));
+        }
+        if let Some(prev) = i.checked_sub(1).map(|p| &migrations[p]) {
+            if m.version <= prev.version {
+                return Err(VtxError::Internal(format!(
+                    "Migration versions must be strictly increasing: {} ({}) follows {} ({})",
+                    m.version, m.name, prev.version, prev.name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 转换为 `VtxPlugin::get_migrations` 的返回值
+///
+/// 每个脚本在 `up` 之后追加执行记录（版本、名称、校验和）写入 `_vtx_migrations`，
+/// 宿主按顺序执行即可，无需感知版本信息。
+///
+/// # Panics
+///
+/// 迁移列表未通过 `validate` 时 panic：`get_migrations` 无法返回错误，而把乱序或空的迁移
+/// 交给宿主执行会留下难以恢复的表结构，因此在插件加载时直接失败。需要自行处理错误时使用
+/// `try_to_host_scripts`。
+///
+/// ```rust,should_panic
+/// use vtx_sdk::migrations::{self, Migration};
+///
+/// let out_of_order = [
+///     Migration::new(2, "add_views", "ALTER TABLE videos ADD COLUMN views INTEGER"),
+///     Migration::new(1, "create_videos", "CREATE TABLE videos (id TEXT PRIMARY KEY)"),
+/// ];
+/// migrations::to_host_scripts(&out_of_order);
+/// ```
+pub fn to_host_scripts(migrations: &[Migration]) -> Vec<String> {
+    try_to_host_scripts(migrations).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// 先 `validate` 再转换；校验失败时返回错误
+pub fn try_to_host_scripts(migrations: &[Migration]) -> VtxResult<Vec<String>> {
+    validate(migrations)?;
+    Ok(migrations.iter().map(host_script).collect())
+}
+
+fn host_script(m: &Migration) -> String {
+    let up = m.up.trim_end();
+    // 脚本末尾可能是未换行的 `--` 注释，先换行再补齐语句结束符
+    let terminator = if up.ends_with(';') { "" } else { "\n;" };
+
+    format!(
+        "{up}{terminator}\n\
+         CREATE TABLE IF NOT EXISTS {table} (\
+         version INTEGER PRIMARY KEY, name TEXT NOT NULL, checksum TEXT NOT NULL);\n\
+         INSERT OR REPLACE INTO {table} (version, name, checksum) VALUES ({version}, '{name}', '{checksum}');",
+        table = HISTORY_TABLE,
+        version = m.version,
+        name = m.name.replace('\'', "''"),
+        checksum = m.checksum(),
+    )
+}
+
+/// 已执行的迁移记录
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+}
+
+/// 读取已执行的迁移记录（记录表不存在时返回空列表）
+pub fn applied() -> VtxResult<Vec<AppliedMigration>> {
+    let exists: Vec<serde_json::Value> = crate::db::query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+        &[&HISTORY_TABLE],
+    )?;
+    if exists.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    crate::db::query(
+        &format!(
+            "SELECT version, name, checksum FROM {} ORDER BY version",
+            HISTORY_TABLE
+        ),
+        &[],
+    )
+}
+
+/// 比对已执行迁移与当前代码中的定义
+///
+/// 以下情况返回 `Conflict`：
+/// - 已执行的迁移在代码中被修改（校验和不一致）
+/// - 已执行的迁移在代码中被删除
+///
+/// 成功时返回尚未执行的迁移版本号。
+pub fn verify_applied(migrations: &[Migration]) -> VtxResult<Vec<u32>> {
+    let applied = applied()?;
+
+    for record in &applied {
+        match migrations.iter().find(|m| m.version == record.version) {
+            Some(m) if m.checksum() != record.checksum => {
+                return Err(VtxError::Conflict(format!(
+                    "Migration {} ({}) was modified after it was applied (checksum {} != {})",
+                    m.version,
+                    m.name,
+                    m.checksum(),
+                    record.checksum
+                )))
+            }
+            Some(_) => {}
+            None => {
+                return Err(VtxError::Conflict(format!(
+                    "Applied migration {} ({}) is missing from the plugin",
+                    record.version, record.name
+                )))
+            }
+        }
+    }
+
+    Ok(migrations
+        .iter()
+        .filter(|m| !applied.iter().any(|r| r.version == m.version))
+        .map(|m| m.version)
+        .collect())
+}
+
+/// 回滚到指定版本（按版本倒序执行已执行迁移的 `down` 脚本，并删除执行记录）
+///
+/// 在单个事务中执行；`down` 脚本经 `db::split_statements` 拆分后逐条调用 `db::execute`
+/// （宿主每次只执行一条语句）。任一迁移缺少 `down` 脚本时不做任何修改并返回错误。
+pub fn rollback_to(migrations: &[Migration], target_version: u32) -> VtxResult<()> {
+    let applied = applied()?;
+    let mut pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|m| m.version > target_version)
+        .filter(|m| applied.iter().any(|r| r.version == m.version))
+        .collect();
+    pending.sort_by_key(|m| std::cmp::Reverse(m.version));
+
+    if let Some(m) = pending.iter().find(|m| m.down.is_none()) {
+        return Err(VtxError::Internal(format!(
+            "Migration {} ({}) has no down script",
+            m.version, m.name
+        )));
+    }
+
+    crate::db::transaction(|tx| {
+        for m in pending {
+            for statement in crate::db::split_statements(m.down.unwrap_or_default()) {
+                tx.execute(statement, &[])?;
+            }
+            tx.execute(
+                &format!("DELETE FROM {} WHERE version = ?", HISTORY_TABLE),
+                &[&m.version],
+            )?;
+        }
+        Ok(())
+    })
+}
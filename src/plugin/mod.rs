@@ -1,8 +1,10 @@
 //! Low-boilerplate plugin exports.
 
+use crate::auth::IntoAuthResult;
 use crate::error::{VtxError, VtxResult};
 use crate::events::PluginEvent;
 use crate::http::{Request, Response, ResponseBuilder};
+use crate::migrations::{self, Migration};
 use crate::{Capabilities, Manifest, UserContext};
 
 /// 更低样板的插件入口 Trait。
@@ -18,8 +20,14 @@ pub trait VtxPlugin {
         Ok(())
     }
 
+    /// 版本化迁移定义（见 `migrations` 模块）
+    fn migrations() -> &'static [Migration] {
+        &[]
+    }
+
+    /// 默认由 `migrations()` 转换而来；直接覆盖时不会写入迁移执行记录。
     fn get_migrations() -> Vec<String> {
-        Vec::new()
+        migrations::to_host_scripts(Self::migrations())
     }
 
     fn get_manifest() -> Manifest;
@@ -36,6 +44,31 @@ pub trait VtxPlugin {
     }
 }
 
+// =====================
+// 导出适配（供 `export_plugin!` 与测试工具共用）
+// =====================
+
+/// `handle` 导出：业务错误经 `ResponseBuilder::error` 映射为错误响应。
+#[doc(hidden)]
+pub fn __handle<P: VtxPlugin>(req: Request) -> Response {
+    match P::handle(req) {
+        Ok(resp) => resp,
+        Err(err) => ResponseBuilder::error(err),
+    }
+}
+
+/// `handle_event` 导出：错误以 `Display` 文本返回宿主。
+#[doc(hidden)]
+pub fn __handle_event<P: VtxPlugin>(event: PluginEvent) -> Result<(), String> {
+    P::handle_event(event).map_err(|err| err.to_string())
+}
+
+/// `authenticate` 导出：错误经 `IntoAuthResult` 降级为 HTTP 状态码。
+#[doc(hidden)]
+pub fn __authenticate<P: VtxPlugin>(headers: &[(String, String)]) -> Result<UserContext, u16> {
+    P::authenticate(headers).into_auth_result()
+}
+
 /// 将实现了 `VtxPlugin` 的类型导出为 WIT `world plugin` 的 Guest。
 ///
 /// - `export_plugin!(MyPlugin)`：生成一个默认的 guest 适配类型并导出。
@@ -49,18 +82,14 @@ macro_rules! export_plugin {
         struct $guest;
 
         impl $crate::bindings::Guest for $guest {
-            fn handle(req: $crate::http::Request) -> $crate::http::Response {
-                match <$plugin as $crate::plugin::VtxPlugin>::handle(req) {
-                    Ok(resp) => resp,
-                    Err(err) => $crate::http::ResponseBuilder::error(err),
-                }
+            fn handle(
+                req: $crate::bindings::vtx::api::types::HttpRequest,
+            ) -> $crate::bindings::vtx::api::types::HttpResponse {
+                $crate::plugin::__handle::<$plugin>(req.into()).into()
             }
 
             fn handle_event(event: $crate::events::PluginEvent) -> Result<(), String> {
-                match <$plugin as $crate::plugin::VtxPlugin>::handle_event(event) {
-                    Ok(()) => Ok(()),
-                    Err(err) => Err(err.to_string()),
-                }
+                $crate::plugin::__handle_event::<$plugin>(event)
             }
 
             fn get_migrations() -> Vec<String> {
@@ -82,8 +111,7 @@ macro_rules! export_plugin {
             fn authenticate(
                 headers: Vec<(String, String)>,
             ) -> Result<$crate::UserContext, u16> {
-                use $crate::auth::IntoAuthResult as _;
-                <$plugin as $crate::plugin::VtxPlugin>::authenticate(&headers).into_auth_result()
+                $crate::plugin::__authenticate::<$plugin>(&headers)
             }
         }
 
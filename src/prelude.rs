@@ -4,20 +4,24 @@
 
 /// 数据库操作工具
 /// 包含 SQL 执行、查询及参数转换特征
-pub use crate::db::{self, ToDbValue};
+pub use crate::db::{self, ToDbValue, TryToDbValue};
 
 /// HTTP 交互工具
-/// 包含请求/响应定义及响应构建器
-pub use crate::http::{Request, Response, ResponseBuilder};
+/// 包含请求/响应定义及响应构建器（`HttpRequest` / `HttpResponse` 为 `PluginTrait` 使用的 WIT 类型）
+pub use crate::http::{HttpRequest, HttpResponse, Request, Response, ResponseBuilder};
+
 pub use crate::http_client::{
     request as http_request, Request as HttpClientRequest, Response as HttpClientResponse,
 };
 
+/// 声明式路由与类型化提取器
+pub use crate::router::{CurrentUser, Form, Header, Json, Params, Path, Query, Router};
+
 /// 鉴权与用户上下文工具及转换特征
 pub use crate::auth::{AuthRequest, IntoAuthResult, UserBuilder};
 
 /// 导出错误类型，方便插件使用 ? 操作符
-pub use crate::error::{VtxError, VtxResult};
+pub use crate::error::{ProblemDetails, VtxError, VtxResult};
 
 /// 核心元数据与宏
 pub use crate::{export, Capabilities, HttpAllowRule, Manifest, UserContext};
@@ -0,0 +1,488 @@
+//! Typed request extractors for router handlers.
+
+use super::Params;
+use crate::context::{self, CurrentUserInfo};
+use crate::error::{VtxError, VtxResult};
+use crate::http::{unsupported_by_host, Request, Response, HOST_FORWARDS_REQUEST_HEADERS};
+use crate::stream::BufferExt;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use std::fmt;
+use std::ops::Deref;
+
+/// 从请求中提取类型化输入（处理函数参数）
+///
+/// 提取失败时返回的错误会直接作为处理函数结果，
+/// 经 `ResponseBuilder::error` 映射为对应状态码（反序列化失败为 400）。
+pub trait FromRequest: Sized {
+    fn from_request(req: &Request, params: &Params) -> VtxResult<Self>;
+
+    /// 供 `Option<T>` 使用：请求中缺少该输入时返回 `Ok(None)`，输入存在但无效时仍返回错误
+    ///
+    /// 内置提取器的「缺少」：缺少必需的路径参数 / 查询参数 / 请求头字段、无请求体、
+    /// 上下文中无用户。默认实现不区分「缺少」，任何提取失败均作为错误返回。
+    fn from_request_optional(req: &Request, params: &Params) -> VtxResult<Option<Self>> {
+        Self::from_request(req, params).map(Some)
+    }
+}
+
+/// 路径参数提取器
+///
+/// - 单个参数：`Path<i64>` / `Path<String>`
+/// - 多个参数：`Path<(String, u32)>`（按模式顺序）或带命名字段的结构体
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<T>(pub T);
+
+/// 查询字符串提取器（按 `application/x-www-form-urlencoded` 解码）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query<T>(pub T);
+
+/// JSON 请求体提取器
+///
+/// ⚠️ 协议 3.6 的宿主不传递请求体，此时提取失败并返回 501 `unsupported_by_host`；
+/// 目前仅在 `testing` feature 的模拟宿主下可用（见 `http::HOST_FORWARDS_REQUEST_HEADERS`）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+/// URL 编码表单请求体提取器（`application/x-www-form-urlencoded`）
+///
+/// ⚠️ 协议 3.6 的宿主不传递请求体，此时提取失败并返回 501 `unsupported_by_host`；
+/// 目前仅在 `testing` feature 的模拟宿主下可用。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Form<T>(pub T);
+
+/// 请求头提取器
+///
+/// Header 名称统一转为小写后参与反序列化，
+/// 结构体字段可用 `#[serde(rename = "x-api-key")]` 对应具体 Header。
+///
+/// ⚠️ 协议 3.6 的宿主不传递请求头，此时提取失败并返回 501 `unsupported_by_host`；
+/// 目前仅在 `testing` feature 的模拟宿主下可用。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header<T>(pub T);
+
+/// 当前登录用户提取器
+///
+/// 上下文中无用户时返回 `AuthDenied(401)`；可选登录场景请使用 `Option<CurrentUser>`。
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub CurrentUserInfo);
+
+macro_rules! impl_deref {
+    ($($ty:ident),*) => {
+        $(
+            impl<T> Deref for $ty<T> {
+                type Target = T;
+
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+impl_deref!(Path, Query, Json, Form, Header);
+
+impl Deref for CurrentUser {
+    type Target = CurrentUserInfo;
+
+    fn deref(&self) -> &CurrentUserInfo {
+        &self.0
+    }
+}
+
+/// 键值对反序列化结果：缺少必需字段时视为输入缺失（`Ok(None)`）
+fn optional_pairs<T>(result: Result<T, PairsError>) -> Result<Option<T>, PairsError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.missing => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Path<T> {
+    fn from_request(req: &Request, params: &Params) -> VtxResult<Self> {
+        Self::from_request_optional(req, params)?
+            .ok_or_else(|| VtxError::Internal("Missing path parameters".to_string()))
+    }
+
+    fn from_request_optional(_req: &Request, params: &Params) -> VtxResult<Option<Self>> {
+        optional_pairs(from_pairs(&params.entries))
+            .map(|v| v.map(Path))
+            .map_err(|e| VtxError::BadRequest(format!("Invalid path parameters: {}", e)))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    fn from_request(req: &Request, _params: &Params) -> VtxResult<Self> {
+        from_pairs(&req.query_pairs())
+            .map(Query)
+            .map_err(|e| VtxError::BadRequest(format!("Invalid query string: {}", e)))
+    }
+
+    fn from_request_optional(req: &Request, _params: &Params) -> VtxResult<Option<Self>> {
+        optional_pairs(from_pairs(&req.query_pairs()))
+            .map(|v| v.map(Query))
+            .map_err(|e| VtxError::BadRequest(format!("Invalid query string: {}", e)))
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Json<T> {
+    fn from_request(req: &Request, _params: &Params) -> VtxResult<Self> {
+        req.require_body()?.read_json().map(Json)
+    }
+
+    fn from_request_optional(req: &Request, _params: &Params) -> VtxResult<Option<Self>> {
+        req.body
+            .as_ref()
+            .map(|body| body.read_json().map(Json))
+            .transpose()
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Form<T> {
+    fn from_request(req: &Request, _params: &Params) -> VtxResult<Self> {
+        req.form().map(Form)
+    }
+
+    fn from_request_optional(req: &Request, _params: &Params) -> VtxResult<Option<Self>> {
+        if req.body.is_none() {
+            return Ok(None);
+        }
+        req.form().map(|v| Some(Form(v)))
+    }
+}
+
+impl<T: DeserializeOwned> Header<T> {
+    fn from_headers(req: &Request) -> Result<T, PairsError> {
+        let headers: Vec<(String, String)> = req
+            .headers
+            .iter()
+            .map(|(k, v)| (k.to_ascii_lowercase(), v.clone()))
+            .collect();
+        from_pairs(&headers)
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Header<T> {
+    fn from_request(req: &Request, _params: &Params) -> VtxResult<Self> {
+        if !HOST_FORWARDS_REQUEST_HEADERS {
+            return Err(unsupported_by_host("Request headers"));
+        }
+        Self::from_headers(req)
+            .map(Header)
+            .map_err(|e| VtxError::BadRequest(format!("Invalid headers: {}", e)))
+    }
+
+    /// 宿主不传递请求头时，请求头视为缺失
+    fn from_request_optional(req: &Request, _params: &Params) -> VtxResult<Option<Self>> {
+        optional_pairs(Self::from_headers(req))
+            .map(|v| v.map(Header))
+            .map_err(|e| VtxError::BadRequest(format!("Invalid headers: {}", e)))
+    }
+}
+
+impl FromRequest for CurrentUser {
+    fn from_request(req: &Request, params: &Params) -> VtxResult<Self> {
+        Self::from_request_optional(req, params)?.ok_or(VtxError::AuthDenied(401))
+    }
+
+    fn from_request_optional(_req: &Request, _params: &Params) -> VtxResult<Option<Self>> {
+        Ok(context::current_user().map(CurrentUser))
+    }
+}
+
+/// 仅在输入缺失时为 `None`（见 `FromRequest::from_request_optional`）
+impl<T: FromRequest> FromRequest for Option<T> {
+    fn from_request(req: &Request, params: &Params) -> VtxResult<Self> {
+        T::from_request_optional(req, params)
+    }
+}
+
+impl<T: FromRequest> FromRequest for VtxResult<T> {
+    fn from_request(req: &Request, params: &Params) -> VtxResult<Self> {
+        Ok(T::from_request(req, params))
+    }
+}
+
+/// 路由处理函数抽象
+///
+/// `Args` 仅用于区分不同签名的实现，支持：
+/// - `Fn(&Request, &Params)`：原始请求与路径参数
+/// - `Fn(E1, E2, ...)`：最多 6 个实现了 `FromRequest` 的提取器
+pub trait Handler<Args>: Send + Sync + 'static {
+    fn call(&self, req: &Request, params: &Params) -> VtxResult<Response>;
+}
+
+/// 原始签名 `Fn(&Request, &Params)` 的标记类型
+pub struct RawArgs;
+
+impl<F> Handler<RawArgs> for F
+where
+    F: Fn(&Request, &Params) -> VtxResult<Response> + Send + Sync + 'static,
+{
+    fn call(&self, req: &Request, params: &Params) -> VtxResult<Response> {
+        self(req, params)
+    }
+}
+
+macro_rules! impl_handler {
+    ($($ty:ident),*) => {
+        impl<F, $($ty,)*> Handler<($($ty,)*)> for F
+        where
+            F: Fn($($ty,)*) -> VtxResult<Response> + Send + Sync + 'static,
+            $($ty: FromRequest,)*
+        {
+            #[allow(unused_variables)]
+            fn call(&self, req: &Request, params: &Params) -> VtxResult<Response> {
+                self($($ty::from_request(req, params)?,)*)
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(T1);
+impl_handler!(T1, T2);
+impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
+
+// =====================
+// 键值对反序列化
+// =====================
+
+/// 将字符串键值对反序列化为目标类型
+///
+/// 值按目标字段类型按需解析（数字、布尔、枚举单元变体等），
+/// 目标为单个值或元组时按顺序取值。
+pub(crate) fn from_pairs<T: DeserializeOwned>(pairs: &[(String, String)]) -> Result<T, PairsError> {
+    T::deserialize(PairsDeserializer { pairs })
+}
+
+/// 键值对反序列化错误
+#[derive(Debug)]
+pub(crate) struct PairsError {
+    message: String,
+    /// 由缺少必需字段（或值）引起，而非值格式错误
+    missing: bool,
+}
+
+impl PairsError {
+    fn new(message: String) -> Self {
+        Self {
+            message,
+            missing: false,
+        }
+    }
+}
+
+impl fmt::Display for PairsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PairsError {}
+
+impl de::Error for PairsError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        PairsError::new(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        PairsError {
+            message: format!("missing field `{}`", field),
+            missing: true,
+        }
+    }
+}
+
+/// 单个字符串值
+struct StrValue<'de>(&'de str);
+
+impl<'de> IntoDeserializer<'de, PairsError> for StrValue<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+                let value = self.0.parse().map_err(|_| {
+                    PairsError::new(format!("cannot parse '{}' as {}", self.0, stringify!($visit).trim_start_matches("visit_")))
+                })?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for StrValue<'de> {
+    type Error = PairsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// 键值对集合
+struct PairsDeserializer<'de> {
+    pairs: &'de [(String, String)],
+}
+
+impl<'de> PairsDeserializer<'de> {
+    fn single(&self) -> Result<StrValue<'de>, PairsError> {
+        match self.pairs {
+            [(_, v)] => Ok(StrValue(v)),
+            [] => Err(PairsError {
+                message: "expected exactly 1 value, found 0".to_string(),
+                missing: true,
+            }),
+            _ => Err(PairsError::new(format!(
+                "expected exactly 1 value, found {}",
+                self.pairs.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_single {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for PairsDeserializer<'de> {
+    type Error = PairsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        let iter = self.pairs.iter().map(|(k, v)| (StrValue(k), StrValue(v)));
+        visitor.visit_map(MapDeserializer::new(iter))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        let iter = self.pairs.iter().map(|(_, v)| StrValue(v));
+        visitor.visit_seq(SeqDeserializer::new(iter))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, PairsError> {
+        visitor.visit_unit()
+    }
+
+    forward_single! {
+        deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
+        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64, deserialize_f32,
+        deserialize_f64, deserialize_char, deserialize_str, deserialize_string,
+        deserialize_bytes, deserialize_byte_buf, deserialize_option, deserialize_identifier,
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, PairsError> {
+        visitor.visit_unit()
+    }
+}
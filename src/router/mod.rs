@@ -0,0 +1,392 @@
+//! Declarative HTTP routing for `VtxPlugin::handle`.
+
+pub mod extract;
+
+pub use extract::{CurrentUser, Form, FromRequest, Handler, Header, Json, Path, Query, RawArgs};
+
+use crate::error::{ProblemDetails, VtxError, VtxResult};
+use crate::http::compress::Compression;
+use crate::http::{percent_decode, Request, Response, ResponseBuilder};
+use std::str::FromStr;
+
+/// 类型擦除后的路由处理函数
+///
+/// 处理函数返回的错误会沿 `VtxPlugin::handle` 交由 `ResponseBuilder::error` 统一映射。
+type BoxedHandler = Box<dyn Fn(&Request, &Params) -> VtxResult<Response> + Send + Sync>;
+
+/// 路径参数集合（按模式中出现的顺序保存）
+///
+/// - `:name` 捕获单个路径段
+/// - `*name` 捕获剩余全部路径（不含前导 `/`）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    entries: Vec<(String, String)>,
+}
+
+impl Params {
+    /// 获取参数值（已做百分号解码）
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 获取必需的参数值
+    ///
+    /// 行为：
+    /// 若参数不存在，返回 `Internal` 错误（通常意味着路由模式与处理函数不一致）。
+    pub fn require(&self, name: &str) -> VtxResult<&str> {
+        self.get(name)
+            .ok_or_else(|| VtxError::Internal(format!("Missing path parameter: {}", name)))
+    }
+
+    /// 将参数解析为目标类型
+    ///
+    /// 解析失败映射为 `BadRequest`（400）。
+    pub fn parse<T>(&self, name: &str) -> VtxResult<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let raw = self.require(name)?;
+        raw.parse::<T>()
+            .map_err(|e| VtxError::BadRequest(format!("Invalid path parameter '{}': {}", name, e)))
+    }
+
+    /// 遍历全部参数
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// 模式中的单个路径段
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    Wildcard(String),
+}
+
+/// 已编译的路径模式
+#[derive(Debug, Clone)]
+struct Pattern {
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Self {
+        let parts = split_path(pattern);
+        let last = parts.len().saturating_sub(1);
+        let segments = parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, part)| {
+                if let Some(name) = part.strip_prefix(':') {
+                    Segment::Param(name.to_string())
+                } else if let Some(name) = part.strip_prefix('*') {
+                    assert!(
+                        i == last,
+                        "wildcard segment must be the last segment in route pattern '{}'",
+                        pattern
+                    );
+                    Segment::Wildcard(name.to_string())
+                } else {
+                    Segment::Static(part.to_string())
+                }
+            })
+            .collect();
+
+        Self { segments }
+    }
+
+    /// 匹配成功时返回参数集合
+    fn matches(&self, path: &[&str]) -> Option<Params> {
+        let mut params = Params::default();
+
+        for (i, seg) in self.segments.iter().enumerate() {
+            match seg {
+                Segment::Static(lit) => {
+                    if path.get(i) != Some(&lit.as_str()) {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    let value = path.get(i)?;
+                    params.entries.push((name.clone(), percent_decode(value)));
+                }
+                Segment::Wildcard(name) => {
+                    let rest = path.get(i..).unwrap_or_default().join("/");
+                    params.entries.push((name.clone(), percent_decode(&rest)));
+                    return Some(params);
+                }
+            }
+        }
+
+        if path.len() == self.segments.len() {
+            Some(params)
+        } else {
+            None
+        }
+    }
+
+    /// 匹配优先级：静态段 > 参数段 > 通配段（逐段比较）
+    fn rank(&self) -> Vec<u8> {
+        self.segments
+            .iter()
+            .map(|seg| match seg {
+                Segment::Static(_) => 2,
+                Segment::Param(_) => 1,
+                Segment::Wildcard(_) => 0,
+            })
+            .collect()
+    }
+}
+
+struct Route {
+    /// `None` 表示匹配任意方法
+    method: Option<String>,
+    pattern: Pattern,
+    handler: BoxedHandler,
+}
+
+/// 声明式 HTTP 路由器
+///
+/// 按方法与路径模式注册处理函数，并在 `dispatch` 时完成：
+/// - 路径参数提取（`/videos/:id`、`/files/*rest`）
+/// - 多条路由同时命中时按「静态段 > 参数段 > 通配段」选择最具体的一条
+/// - 路径命中但方法不匹配时返回 405，并通过 `Allow` 头列出可用方法
+/// - 未注册 HEAD 路由时，HEAD 请求由同路径的 GET 处理函数响应（丢弃响应体）
+/// - 全部未命中时返回 fallback（默认 `ResponseBuilder::not_found()`）
+///
+/// 协议 3.6 的宿主只传递 method / path / query：`Path`、`Query`、`CurrentUser` 提取器可正常使用，
+/// `Json`、`Form`、`Header` 依赖请求体或请求头，在真实宿主上返回 501 `unsupported_by_host`，
+/// 目前仅在 `testing` feature 的模拟宿主下可用（见 `http::HOST_FORWARDS_REQUEST_HEADERS`）。
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::OnceLock;
+/// use vtx_sdk::prelude::*;
+/// use vtx_sdk::router::{Params, Router};
+///
+/// fn routes() -> Router {
+///     Router::new()
+///         .get("/videos", |_req: &Request, _params: &Params| Ok(ResponseBuilder::status(200)))
+///         .get("/videos/:id", |Path(id): Path<i64>| Ok(ResponseBuilder::json(&id)))
+/// }
+///
+/// fn handle(req: Request) -> VtxResult<Response> {
+///     static ROUTER: OnceLock<Router> = OnceLock::new();
+///     ROUTER.get_or_init(routes).dispatch(req)
+/// }
+/// ```
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Option<BoxedHandler>,
+    compression: Option<Compression>,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Router {
+    /// 创建空路由器
+    pub fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: None,
+            compression: None,
+        }
+    }
+
+    /// 注册指定方法的路由（方法名大小写不敏感）
+    ///
+    /// # Panics
+    /// 若通配段 `*name` 不在模式末尾，将在注册时 panic。
+    pub fn route<H, Args>(mut self, method: &str, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.routes.push(Route {
+            method: Some(method.to_ascii_uppercase()),
+            pattern: Pattern::parse(pattern),
+            handler: boxed(handler),
+        });
+        self
+    }
+
+    /// 注册匹配任意方法的路由
+    pub fn any<H, Args>(mut self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.routes.push(Route {
+            method: None,
+            pattern: Pattern::parse(pattern),
+            handler: boxed(handler),
+        });
+        self
+    }
+
+    pub fn get<H, Args>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.route("GET", pattern, handler)
+    }
+
+    pub fn post<H, Args>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.route("POST", pattern, handler)
+    }
+
+    pub fn put<H, Args>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.route("PUT", pattern, handler)
+    }
+
+    pub fn patch<H, Args>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.route("PATCH", pattern, handler)
+    }
+
+    pub fn delete<H, Args>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.route("DELETE", pattern, handler)
+    }
+
+    /// 设置未命中任何路由时的处理函数（默认返回 404）
+    pub fn fallback<H, Args>(mut self, handler: H) -> Self
+    where
+        H: Handler<Args>,
+    {
+        self.fallback = Some(boxed(handler));
+        self
+    }
+
+    /// 按 `Accept-Encoding` 压缩处理函数返回的成功响应（详见 `http::compress`）
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// 分发请求到匹配的处理函数
+    ///
+    /// 签名与 `VtxPlugin::handle` 一致，可直接作为其实现体。
+    pub fn dispatch(&self, req: Request) -> VtxResult<Response> {
+        let resp = self.route_request(&req)?;
+        Ok(match &self.compression {
+            Some(compression) => compression.apply(&req, resp),
+            None => resp,
+        })
+    }
+
+    fn route_request(&self, req: &Request) -> VtxResult<Response> {
+        let path = split_path(&req.path);
+        let method = req.method.to_ascii_uppercase();
+
+        let (best, mut allowed) = self.find(&path, &method);
+        if let Some((route, params)) = best {
+            return (route.handler)(req, &params);
+        }
+
+        // 未注册 HEAD 路由时由 GET 处理函数响应，并丢弃响应体
+        if method == "HEAD" {
+            if let (Some((route, params)), _) = self.find(&path, "GET") {
+                let mut resp = (route.handler)(req, &params)?;
+                resp.body = None;
+                return Ok(resp);
+            }
+        }
+
+        if !allowed.is_empty() {
+            if allowed.contains(&"GET") && !allowed.contains(&"HEAD") {
+                allowed.push("HEAD");
+            }
+            return Ok(method_not_allowed(&method, &allowed));
+        }
+
+        match &self.fallback {
+            Some(handler) => handler(req, &Params::default()),
+            None => Ok(ResponseBuilder::not_found()),
+        }
+    }
+
+    /// 查找路径与方法均匹配的最具体路由，并收集路径匹配但方法不同的路由方法
+    fn find(&self, path: &[&str], method: &str) -> (Option<(&Route, Params)>, Vec<&str>) {
+        let mut best: Option<(&Route, Params, Vec<u8>)> = None;
+        let mut allowed: Vec<&str> = Vec::new();
+
+        for route in &self.routes {
+            let Some(params) = route.pattern.matches(path) else {
+                continue;
+            };
+
+            match &route.method {
+                Some(m) if m != method => {
+                    if !allowed.contains(&m.as_str()) {
+                        allowed.push(m.as_str());
+                    }
+                }
+                _ => {
+                    let rank = route.pattern.rank();
+                    let better = match &best {
+                        Some((_, _, best_rank)) => rank > *best_rank,
+                        None => true,
+                    };
+                    if better {
+                        best = Some((route, params, rank));
+                    }
+                }
+            }
+        }
+
+        (best.map(|(route, params, _)| (route, params)), allowed)
+    }
+}
+
+fn boxed<H, Args>(handler: H) -> BoxedHandler
+where
+    H: Handler<Args>,
+{
+    Box::new(move |req: &Request, params: &Params| handler.call(req, params))
+}
+
+/// 构造 405 响应（问题详情与 `ResponseBuilder::error` 同构，并通过 `Allow` 头及扩展成员 `allow` 列出可用方法）
+fn method_not_allowed(method: &str, allowed: &[&str]) -> Response {
+    let problem = ProblemDetails::new(405, "method_not_allowed")
+        .detail(format!(
+            "Method {} is not allowed for this resource",
+            method
+        ))
+        .extension("allow", allowed);
+
+    let mut resp = ResponseBuilder::problem(&problem);
+    resp.set_header("Allow", allowed.join(", "));
+    resp
+}
+
+/// 拆分路径为非空段（忽略首尾及重复的 `/`）
+fn split_path(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
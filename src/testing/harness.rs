@@ -0,0 +1,292 @@
+//! Request/response harness driving `VtxPlugin` implementations natively.
+
+use super::MockHost;
+use crate::bindings::vtx::api::events::{EventContext, VtxEvent};
+use crate::error::{VtxError, VtxResult};
+use crate::http::{Request, Response};
+use crate::plugin::{self, VtxPlugin};
+use crate::stream::{self, BufferExt};
+use crate::UserContext;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// 插件测试驱动器
+///
+/// 调用与 `export_plugin!` 生成代码完全相同的导出适配路径：
+/// - `handle`：业务错误经 `ResponseBuilder::error` 映射为错误响应
+/// - `handle_event`：错误转为宿主可见的字符串
+/// - `authenticate`：错误经 `IntoAuthResult` 降级为状态码
+///
+/// 创建时会重置当前线程的 Mock 宿主，可通过 `host()` 预置数据与断言副作用。
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::prelude::*;
+/// use vtx_sdk::testing::PluginHarness;
+///
+/// struct Echo;
+///
+/// impl VtxPlugin for Echo {
+///     fn handle(req: Request) -> VtxResult<Response> {
+///         match req.query_param("name") {
+///             Some(name) => Ok(ResponseBuilder::json(&serde_json::json!({ "hello": name }))),
+///             None => Err(VtxError::NotFound("name".into())),
+///         }
+///     }
+///
+///     fn get_manifest() -> Manifest {
+///         unimplemented!()
+///     }
+///
+///     fn get_capabilities() -> Capabilities {
+///         unimplemented!()
+///     }
+/// }
+///
+/// let harness = PluginHarness::<Echo>::new();
+///
+/// let resp = harness.get("/hello?name=vtx").send();
+/// assert_eq!(resp.status(), 200);
+/// assert_eq!(resp.json::<serde_json::Value>()["hello"], "vtx");
+///
+/// assert_eq!(harness.get("/hello").send().status(), 404);
+/// ```
+pub struct PluginHarness<P: VtxPlugin> {
+    host: MockHost,
+    _plugin: PhantomData<P>,
+}
+
+impl<P: VtxPlugin> Default for PluginHarness<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: VtxPlugin> PluginHarness<P> {
+    /// 创建测试驱动器（重置 Mock 宿主）
+    pub fn new() -> Self {
+        Self {
+            host: MockHost::new(),
+            _plugin: PhantomData,
+        }
+    }
+
+    /// Mock 宿主控制句柄
+    pub fn host(&self) -> &MockHost {
+        &self.host
+    }
+
+    /// 构造指定方法的请求；`path` 可携带 `?query`
+    pub fn request(&self, method: &str, path: &str) -> TestRequest<'_, P> {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        TestRequest {
+            harness: self,
+            method: method.to_ascii_uppercase(),
+            path: path.to_string(),
+            query: query.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn get(&self, path: &str) -> TestRequest<'_, P> {
+        self.request("GET", path)
+    }
+
+    pub fn post(&self, path: &str) -> TestRequest<'_, P> {
+        self.request("POST", path)
+    }
+
+    pub fn put(&self, path: &str) -> TestRequest<'_, P> {
+        self.request("PUT", path)
+    }
+
+    pub fn patch(&self, path: &str) -> TestRequest<'_, P> {
+        self.request("PATCH", path)
+    }
+
+    pub fn delete(&self, path: &str) -> TestRequest<'_, P> {
+        self.request("DELETE", path)
+    }
+
+    /// 直接分发一个已构造的请求
+    pub fn handle(&self, req: Request) -> TestResponse {
+        TestResponse::from_response(plugin::__handle::<P>(req))
+    }
+
+    /// 投递事件（payload 自动序列化为 JSON）
+    pub fn emit<T: serde::Serialize>(&self, topic: &str, payload: &T) -> Result<(), String> {
+        let payload = serde_json::to_string(payload).map_err(|e| e.to_string())?;
+        self.handle_event(VtxEvent {
+            id: format!("test-event-{}", topic),
+            topic: topic.to_string(),
+            source: "vtx-sdk-testing".to_string(),
+            payload,
+            context: EventContext {
+                user_id: None,
+                username: None,
+                request_id: None,
+            },
+            occurred_at: 0,
+        })
+    }
+
+    /// 投递一个完整的事件
+    pub fn handle_event(&self, event: VtxEvent) -> Result<(), String> {
+        plugin::__handle_event::<P>(event)
+    }
+
+    /// 调用鉴权导出
+    pub fn authenticate(&self, headers: &[(&str, &str)]) -> Result<UserContext, u16> {
+        let headers: Vec<(String, String)> = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        plugin::__authenticate::<P>(&headers)
+    }
+}
+
+/// 测试请求构造器
+pub struct TestRequest<'a, P: VtxPlugin> {
+    harness: &'a PluginHarness<P>,
+    method: String,
+    path: String,
+    query: String,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl<P: VtxPlugin> TestRequest<'_, P> {
+    /// 追加一个查询参数（自动百分号编码）
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+        self.query.push_str(&percent_encode(key));
+        self.query.push('=');
+        self.query.push_str(&percent_encode(value));
+        self
+    }
+
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// 设置原始请求体
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// 设置 JSON 请求体，并补充 `Content-Type: application/json`
+    pub fn json<T: serde::Serialize>(self, data: &T) -> Self {
+        let body = serde_json::to_vec(data).expect("failed to serialize test request body");
+        self.header("Content-Type", "application/json").body(body)
+    }
+
+    /// 构造 SDK 请求（不发送）
+    pub fn build(self) -> Request {
+        Request {
+            method: self.method,
+            path: self.path,
+            query: self.query,
+            headers: self.headers,
+            body: self.body.map(stream::memory_buffer),
+        }
+    }
+
+    /// 发送请求并收集响应
+    pub fn send(self) -> TestResponse {
+        let harness = self.harness;
+        harness.handle(self.build())
+    }
+}
+
+/// 测试响应（响应体已完整读出）
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl TestResponse {
+    fn from_response(resp: Response) -> Self {
+        Self {
+            status: resp.status,
+            body: resp.body.as_ref().map(|b| b.read_all()),
+            headers: resp.headers,
+        }
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// 获取响应头 (Case-insensitive)
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 全部响应头（保持设置顺序）
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// 是否包含响应体（区分「无 body」与「空 body」）
+    pub fn has_body(&self) -> bool {
+        self.body.is_some()
+    }
+
+    /// 响应体字节（无 body 时为空）
+    pub fn bytes(&self) -> &[u8] {
+        self.body.as_deref().unwrap_or_default()
+    }
+
+    /// 按 UTF-8 解码响应体（非法 UTF-8 时 panic）
+    pub fn text(&self) -> String {
+        String::from_utf8(self.bytes().to_vec()).expect("response body is not valid UTF-8")
+    }
+
+    /// 将响应体反序列化为目标类型（失败时 panic 并附带原始内容）
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        self.try_json()
+            .unwrap_or_else(|e| panic!("{}; body: {}", e, String::from_utf8_lossy(self.bytes())))
+    }
+
+    /// 将响应体反序列化为目标类型
+    pub fn try_json<T: DeserializeOwned>(&self) -> VtxResult<T> {
+        serde_json::from_slice(self.bytes())
+            .map_err(|e| VtxError::SerializationError(e.to_string()))
+    }
+
+    /// 断言状态码，便于链式断言
+    #[track_caller]
+    pub fn assert_status(&self, expected: u16) -> &Self {
+        assert_eq!(
+            self.status,
+            expected,
+            "unexpected status; body: {}",
+            String::from_utf8_lossy(self.bytes())
+        );
+        self
+    }
+}
+
+/// 查询参数百分号编码（保留 RFC 3986 unreserved 字符）
+fn percent_encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for b in input.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
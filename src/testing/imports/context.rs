@@ -0,0 +1,8 @@
+//! Mock `context` interface.
+
+use crate::bindings::vtx::api::auth_types::CurrentUser;
+use crate::testing::state::with_state;
+
+pub fn get_current_user() -> Option<CurrentUser> {
+    with_state(|s| s.current_user.clone())
+}
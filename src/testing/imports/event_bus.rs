@@ -0,0 +1,23 @@
+//! Mock `event-bus` interface.
+
+use crate::testing::state::with_state;
+use crate::testing::PublishedEvent;
+
+/// 记录发布的事件；与宿主一致，payload 必须是合法 JSON。
+pub fn publish_event(topic: &str, payload: &str) -> Result<(), String> {
+    with_state(|s| {
+        if let Some(err) = s.publish_failures.pop_front() {
+            return Err(err);
+        }
+
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(payload) {
+            return Err(format!("Invalid event payload: {}", e));
+        }
+
+        s.events.push(PublishedEvent {
+            topic: topic.to_string(),
+            payload: payload.to_string(),
+        });
+        Ok(())
+    })
+}
@@ -0,0 +1,42 @@
+//! Mock `ffmpeg` interface.
+
+use super::stream_io::Buffer;
+use crate::testing::state::{with_state, BufferData};
+use crate::testing::RecordedFfmpegCall;
+
+#[derive(Clone, Debug)]
+pub struct FfmpegOption {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TranscodeProfile {
+    pub profile: String,
+    pub input_id: String,
+    pub options: Vec<FfmpegOption>,
+}
+
+/// 记录调用参数，并按脚本顺序返回预设的 stdout（pipe Buffer）或错误。
+pub fn execute(params: &TranscodeProfile) -> Result<Buffer, String> {
+    let handle = with_state(|s| {
+        s.ffmpeg_calls.push(RecordedFfmpegCall {
+            profile: params.profile.clone(),
+            input_id: params.input_id.clone(),
+            options: params
+                .options
+                .iter()
+                .map(|o| (o.key.clone(), o.value.clone()))
+                .collect(),
+        });
+
+        let output = s
+            .ffmpeg_results
+            .pop_front()
+            .unwrap_or_else(|| Err("FFmpeg result not scripted in mock host".to_string()))?;
+        Ok::<_, String>(s.alloc_buffer(BufferData::Pipe(output.into())))
+    })?;
+
+    // SAFETY: 句柄刚由 Mock 宿主分配，且尚未被任何 Buffer 持有。
+    Ok(unsafe { Buffer::from_handle(handle) })
+}
@@ -0,0 +1,31 @@
+//! Mock `http-client` interface.
+
+use super::stream_io::{create_memory_buffer, Buffer};
+use crate::bindings::vtx::api::types::{HttpClientRequest, HttpClientResponse};
+use crate::stream::BufferExt;
+use crate::testing::state::with_state;
+use crate::testing::RecordedHttpRequest;
+
+/// 记录出站请求，并按脚本顺序返回预设响应或错误。
+pub fn request(req: HttpClientRequest) -> Result<HttpClientResponse, String> {
+    // 请求体在状态借用之外读取，Buffer 的读取/析构同样需要访问状态
+    let body = req.body.as_ref().map(Buffer::read_all);
+
+    let scripted = with_state(|s| {
+        s.http_requests.push(RecordedHttpRequest {
+            method: req.method.clone(),
+            url: req.url.clone(),
+            headers: req.headers.clone(),
+            body,
+        });
+        s.http_results.pop_front()
+    });
+    drop(req);
+
+    let resp = scripted.unwrap_or_else(|| Err("HTTP response not scripted in mock host".into()))?;
+    Ok(HttpClientResponse {
+        status: resp.status,
+        headers: resp.headers,
+        body: resp.body.map(|b| create_memory_buffer(&b)),
+    })
+}
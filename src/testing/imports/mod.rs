@@ -0,0 +1,11 @@
+//! Native replacements for the WIT host imports.
+//!
+//! 各子模块与 `wit-bindgen` 生成的导入接口保持相同的类型与函数签名，
+//! 由 `build.rs` 通过 `with` 选项在 `testing` 特性下替换真实宿主导入。
+
+pub mod context;
+pub mod event_bus;
+pub mod ffmpeg;
+pub mod http_client;
+pub mod sql;
+pub mod stream_io;
@@ -0,0 +1,95 @@
+//! Mock `sql` interface backed by an in-memory SQLite database.
+
+use crate::testing::state::with_state;
+use rusqlite::types::{Value, ValueRef};
+
+#[derive(Clone, Debug)]
+pub enum DbValue {
+    Text(String),
+    Integer(i64),
+    Real(f64),
+    NullVal,
+}
+
+impl From<&DbValue> for Value {
+    fn from(v: &DbValue) -> Self {
+        match v {
+            DbValue::Text(s) => Value::Text(s.clone()),
+            DbValue::Integer(i) => Value::Integer(*i),
+            DbValue::Real(f) => Value::Real(*f),
+            DbValue::NullVal => Value::Null,
+        }
+    }
+}
+
+/// 执行单条非查询语句（与真实宿主的 prepare / execute 一致）
+///
+/// 多条语句（尾部仅剩空白或注释除外）返回错误，而不是静默按批处理执行；
+/// 返回值为该语句实际影响的行数（DDL 为 0）。
+pub fn execute(statement: &str, params: &[DbValue]) -> Result<u64, String> {
+    with_state(|s| {
+        if s.sql_read_only {
+            return Err("Permission denied: database is read-only for this plugin".into());
+        }
+
+        let conn = s.db()?;
+        let mut batch = rusqlite::Batch::new(conn, statement);
+        let mut stmt = batch
+            .next()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "SQL statement is empty".to_string())?;
+        if !matches!(batch.next(), Ok(None)) {
+            return Err(
+                "Multiple statements provided: execute runs exactly one statement".to_string(),
+            );
+        }
+
+        // DDL 不会重置 sqlite3_changes()，以 total_changes 判断本条语句是否修改了行
+        let before = conn.total_changes();
+        let values: Vec<Value> = params.iter().map(Value::from).collect();
+        let changed = stmt
+            .execute(rusqlite::params_from_iter(values))
+            .map_err(|e| e.to_string())?;
+        Ok(if conn.total_changes() == before {
+            0
+        } else {
+            changed as u64
+        })
+    })
+}
+
+/// 执行查询并将结果编码为 JSON 对象数组（列名 → 值）。
+pub fn query_json(statement: &str, params: &[DbValue]) -> Result<String, String> {
+    with_state(|s| {
+        let conn = s.db()?;
+        let mut stmt = conn.prepare(statement).map_err(|e| e.to_string())?;
+        let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+        let values: Vec<Value> = params.iter().map(Value::from).collect();
+        let mut rows = stmt
+            .query(rusqlite::params_from_iter(values))
+            .map_err(|e| e.to_string())?;
+
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            let mut obj = serde_json::Map::new();
+            for (i, name) in columns.iter().enumerate() {
+                let value = row.get_ref(i).map_err(|e| e.to_string())?;
+                obj.insert(name.clone(), to_json(value));
+            }
+            out.push(serde_json::Value::Object(obj));
+        }
+
+        serde_json::to_string(&out).map_err(|e| e.to_string())
+    })
+}
+
+fn to_json(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => i.into(),
+        ValueRef::Real(f) => f.into(),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+        ValueRef::Blob(b) => b.to_vec().into(),
+    }
+}
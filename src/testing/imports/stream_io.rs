@@ -0,0 +1,110 @@
+//! Mock `stream-io` interface.
+
+use crate::testing::state::{try_with_state, with_state, BufferData};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Mock 宿主中的 Buffer 资源句柄
+///
+/// 与生成的资源类型一致：句柄被 `take_handle` 取走后析构不再释放宿主侧资源。
+#[derive(Debug)]
+pub struct Buffer {
+    handle: AtomicU32,
+}
+
+impl Buffer {
+    /// # Safety
+    /// `handle` 必须是 Mock 宿主分配且未被其它 `Buffer` 持有的句柄。
+    pub unsafe fn from_handle(handle: u32) -> Self {
+        Self {
+            handle: AtomicU32::new(handle),
+        }
+    }
+
+    pub fn take_handle(&self) -> u32 {
+        self.handle.swap(u32::MAX, Ordering::Relaxed)
+    }
+
+    pub fn handle(&self) -> u32 {
+        self.handle.load(Ordering::Relaxed)
+    }
+
+    fn with_data<R>(&self, f: impl FnOnce(&mut BufferData) -> R) -> R {
+        let handle = self.handle();
+        with_state(|s| {
+            let data = s
+                .buffers
+                .get_mut(&handle)
+                .unwrap_or_else(|| panic!("invalid buffer handle: {}", handle));
+            f(data)
+        })
+    }
+
+    pub fn size(&self) -> u64 {
+        self.with_data(|data| match data {
+            BufferData::Memory(bytes) => bytes.len() as u64,
+            BufferData::File(file) => file.borrow().len() as u64,
+            BufferData::Pipe(_) => 0,
+        })
+    }
+
+    pub fn read(&self, offset: u64, max_bytes: u64) -> Vec<u8> {
+        fn slice(bytes: &[u8], offset: u64, max_bytes: u64) -> Vec<u8> {
+            let start = usize::try_from(offset)
+                .unwrap_or(usize::MAX)
+                .min(bytes.len());
+            let len = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+            let end = start.saturating_add(len).min(bytes.len());
+            bytes[start..end].to_vec()
+        }
+
+        self.with_data(|data| match data {
+            BufferData::Memory(bytes) => slice(bytes, offset, max_bytes),
+            BufferData::File(file) => slice(&file.borrow(), offset, max_bytes),
+            BufferData::Pipe(queue) => {
+                let n = usize::try_from(max_bytes)
+                    .unwrap_or(usize::MAX)
+                    .min(queue.len());
+                queue.drain(..n).collect()
+            }
+        })
+    }
+
+    pub fn write(&self, data: &[u8]) -> u64 {
+        self.with_data(|buf| match buf {
+            BufferData::Memory(bytes) => bytes.extend_from_slice(data),
+            BufferData::File(file) => file.borrow_mut().extend_from_slice(data),
+            BufferData::Pipe(queue) => queue.extend(data),
+        });
+        data.len() as u64
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        let handle = self.handle();
+        if handle != u32::MAX {
+            try_with_state(|s| s.buffers.remove(&handle));
+        }
+    }
+}
+
+pub fn open_file(uuid: &str) -> Result<Buffer, String> {
+    let handle = with_state(|s| {
+        let file = s
+            .files
+            .get(uuid)
+            .cloned()
+            .ok_or_else(|| format!("UUID not found: {}", uuid))?;
+        Ok::<_, String>(s.alloc_buffer(BufferData::File(file)))
+    })?;
+
+    // SAFETY: 句柄刚由 Mock 宿主分配，且尚未被任何 Buffer 持有。
+    Ok(unsafe { Buffer::from_handle(handle) })
+}
+
+pub fn create_memory_buffer(data: &[u8]) -> Buffer {
+    let handle = with_state(|s| s.alloc_buffer(BufferData::Memory(data.to_vec())));
+
+    // SAFETY: 句柄刚由 Mock 宿主分配，且尚未被任何 Buffer 持有。
+    unsafe { Buffer::from_handle(handle) }
+}
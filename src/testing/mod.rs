@@ -0,0 +1,282 @@
+//! In-process mock host for native plugin unit tests.
+//!
+//! 启用 `testing` 特性后，在非 Wasm 目标上所有 `host::*` 封装
+//! （`db`、`stream`、`ffmpeg`、`context`、`event_bus`、`http_client`）
+//! 都会调用本模块提供的 Mock 宿主，而不是 Wasm 导入：
+//!
+//! - `sql`：进程内 SQLite（内存数据库），返回真实的 SQLite 错误消息
+//! - `stream_io`：UUID → 文件内容映射，以及 memory / pipe Buffer
+//! - `event_bus`：记录已发布的事件
+//! - `context`：可设置的当前用户
+//! - `ffmpeg` / `http_client`：按脚本顺序返回预设结果，并记录调用参数
+//!
+//! 状态按线程隔离，`cargo test` 并行执行的测试互不影响。
+//! 端到端驱动插件导出（请求 → 响应）请使用 `PluginHarness`。
+//!
+//! ```toml
+//! [dev-dependencies]
+//! vtx-sdk = { version = "0.1.8", features = ["testing"] }
+//! ```
+
+#[doc(hidden)]
+pub mod imports;
+
+mod harness;
+mod state;
+
+pub use harness::{PluginHarness, TestRequest, TestResponse};
+
+use crate::bindings::vtx::api::auth_types::CurrentUser;
+use crate::error::VtxError;
+use crate::migrations::{self, Migration};
+use crate::stream::StreamBuffer;
+use state::{with_state, BufferData};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// 通过 `event_bus` 发布的事件记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedEvent {
+    pub topic: String,
+    /// 原始 JSON 字符串
+    pub payload: String,
+}
+
+impl PublishedEvent {
+    /// 将 payload 解析为 JSON 值
+    pub fn payload_value(&self) -> serde_json::Value {
+        serde_json::from_str(&self.payload).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// FFmpeg 调用记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedFfmpegCall {
+    pub profile: String,
+    pub input_id: String,
+    /// `(key, value)`，flag 类选项的 value 为 `None`
+    pub options: Vec<(String, Option<String>)>,
+}
+
+/// 出站 HTTP 请求记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedHttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// 预设的出站 HTTP 响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockHttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl MockHttpResponse {
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// 以 JSON 作为响应体（序列化失败时 panic，仅用于测试）
+    pub fn json<T: serde::Serialize>(self, data: &T) -> Self {
+        let body = serde_json::to_vec(data).expect("failed to serialize mock response body");
+        self.header("content-type", "application/json").body(body)
+    }
+}
+
+/// Mock 宿主控制句柄
+///
+/// `MockHost::new()` 会重置当前线程的 Mock 状态，建议在每个测试开头调用。
+///
+/// # Example
+///
+/// ```rust
+/// use vtx_sdk::prelude::*;
+/// use vtx_sdk::testing::MockHost;
+///
+/// let host = MockHost::new();
+/// host.execute_sql("CREATE TABLE videos (id INTEGER PRIMARY KEY, title TEXT)").unwrap();
+///
+/// db::execute("INSERT INTO videos (title) VALUES (?)", &[&"intro"]).unwrap();
+/// let titles: Vec<serde_json::Value> = db::query("SELECT title FROM videos", &[]).unwrap();
+/// assert_eq!(titles[0]["title"], "intro");
+///
+/// event_bus::publish_json("video.created", &serde_json::json!({ "id": 1 })).unwrap();
+/// assert_eq!(host.published_events()[0].topic, "video.created");
+/// ```
+pub struct MockHost {
+    // 状态按线程隔离，句柄本身不应跨线程传递
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Default for MockHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockHost {
+    /// 重置当前线程的 Mock 宿主并返回控制句柄
+    pub fn new() -> Self {
+        state::reset();
+        Self {
+            _not_send: PhantomData,
+        }
+    }
+
+    /// 重置全部状态（数据库、文件、事件、脚本等）
+    pub fn reset(&self) {
+        state::reset();
+    }
+
+    // --- context ---
+
+    /// 设置 `context::current_user()` 的返回值
+    pub fn set_current_user(&self, user: Option<CurrentUser>) {
+        with_state(|s| s.current_user = user);
+    }
+
+    /// 以指定身份登录（便捷方法）
+    pub fn login(&self, user_id: &str, username: &str, groups: &[&str]) {
+        self.set_current_user(Some(CurrentUser {
+            user_id: user_id.to_string(),
+            username: username.to_string(),
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+        }));
+    }
+
+    // --- sql ---
+
+    /// 直接在 Mock 数据库上执行 SQL（可包含多条语句，常用于准备测试数据）
+    pub fn execute_sql(&self, sql: &str) -> Result<(), VtxError> {
+        with_state(|s| s.db()?.execute_batch(sql).map_err(|e| e.to_string()))
+            .map_err(VtxError::DatabaseError)
+    }
+
+    /// 按宿主方式依次执行迁移脚本（`migrations::to_host_scripts` 的输出）
+    ///
+    /// 用于在发布前发现错误的迁移 SQL；失败时错误消息包含迁移版本与名称。
+    ///
+    /// ```rust
+    /// use vtx_sdk::migrations::Migration;
+    /// use vtx_sdk::testing::MockHost;
+    ///
+    /// static MIGRATIONS: &[Migration] = &[
+    ///     Migration::new(
+    ///         1,
+    ///         "create_videos",
+    ///         "CREATE TABLE videos (id TEXT PRIMARY KEY, title TEXT);
+    ///          CREATE INDEX idx_videos_title ON videos (title);",
+    ///     )
+    ///     .with_down("DROP INDEX idx_videos_title; DROP TABLE videos;"),
+    /// ];
+    ///
+    /// let host = MockHost::new();
+    /// host.check_migrations(MIGRATIONS).unwrap();
+    ///
+    /// let broken = [Migration::new(1, "typo", "CREATE TABL videos (id TEXT)")];
+    /// assert!(MockHost::new().run_migrations(&broken).is_err());
+    /// ```
+    pub fn run_migrations(&self, migrations: &[Migration]) -> Result<(), VtxError> {
+        let scripts = migrations::try_to_host_scripts(migrations)?;
+        for (m, script) in migrations.iter().zip(scripts) {
+            self.execute_sql(&script).map_err(|e| match e {
+                VtxError::DatabaseError(msg) => VtxError::DatabaseError(format!(
+                    "Migration {} ({}) failed: {}",
+                    m.version, m.name, msg
+                )),
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// 往返校验：执行全部 `up` → 逆序执行全部 `down` → 再次执行全部 `up`
+    ///
+    /// 要求每个迁移都提供 `down` 脚本。
+    pub fn check_migrations(&self, migrations: &[Migration]) -> Result<(), VtxError> {
+        self.run_migrations(migrations)?;
+        migrations::rollback_to(migrations, 0)?;
+        self.run_migrations(migrations)
+    }
+
+    /// 模拟只读（Restricted）策略：开启后 `db::execute` 返回权限错误
+    pub fn set_sql_read_only(&self, read_only: bool) {
+        with_state(|s| s.sql_read_only = read_only);
+    }
+
+    // --- stream_io ---
+
+    /// 注册一个可通过 `stream::open_file(uuid)` 打开的文件
+    pub fn add_file(&self, uuid: impl Into<String>, data: impl Into<Vec<u8>>) {
+        let file = Rc::new(RefCell::new(data.into()));
+        with_state(|s| s.files.insert(uuid.into(), file));
+    }
+
+    /// 读取文件当前内容（包含插件追加写入的数据）
+    pub fn file(&self, uuid: &str) -> Option<Vec<u8>> {
+        with_state(|s| s.files.get(uuid).map(|f| f.borrow().clone()))
+    }
+
+    /// 创建 pipe 模式的 Buffer（`size()` 为 0，读空即 EOF）
+    pub fn pipe_buffer(&self, data: impl Into<Vec<u8>>) -> StreamBuffer {
+        let handle = with_state(|s| s.alloc_buffer(BufferData::Pipe(data.into().into())));
+
+        // SAFETY: 句柄刚由 Mock 宿主分配，且尚未被任何 Buffer 持有。
+        unsafe { StreamBuffer::from_handle(handle) }
+    }
+
+    // --- event_bus ---
+
+    /// 已发布的事件（按发布顺序）
+    pub fn published_events(&self) -> Vec<PublishedEvent> {
+        with_state(|s| s.events.clone())
+    }
+
+    /// 让下一次 `publish_event` 返回指定的宿主错误消息
+    pub fn fail_next_publish(&self, message: impl Into<String>) {
+        with_state(|s| s.publish_failures.push_back(message.into()));
+    }
+
+    // --- ffmpeg ---
+
+    /// 追加一次 FFmpeg 调用的预设结果（`Ok` 为 stdout 内容，`Err` 为宿主错误消息）
+    pub fn script_ffmpeg(&self, result: Result<Vec<u8>, String>) {
+        with_state(|s| s.ffmpeg_results.push_back(result));
+    }
+
+    /// 已发生的 FFmpeg 调用（按调用顺序）
+    pub fn ffmpeg_calls(&self) -> Vec<RecordedFfmpegCall> {
+        with_state(|s| s.ffmpeg_calls.clone())
+    }
+
+    // --- http_client ---
+
+    /// 追加一次出站 HTTP 请求的预设结果
+    pub fn script_http(&self, result: Result<MockHttpResponse, String>) {
+        with_state(|s| s.http_results.push_back(result));
+    }
+
+    /// 已发生的出站 HTTP 请求（按调用顺序）
+    pub fn http_requests(&self) -> Vec<RecordedHttpRequest> {
+        with_state(|s| s.http_requests.clone())
+    }
+}
@@ -0,0 +1,87 @@
+//! Thread-local state backing the mock host.
+
+use crate::bindings::vtx::api::auth_types::CurrentUser;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+use super::{MockHttpResponse, PublishedEvent, RecordedFfmpegCall, RecordedHttpRequest};
+
+/// Mock Buffer 的底层存储
+pub(crate) enum BufferData {
+    /// 内存 Buffer：按偏移读取，写入追加
+    Memory(Vec<u8>),
+    /// 文件 Buffer：与文件表共享内容，写入追加到文件
+    File(Rc<RefCell<Vec<u8>>>),
+    /// 管道 Buffer：`size()` 为 0，读取时按序消费，读空即 EOF
+    Pipe(VecDeque<u8>),
+}
+
+#[derive(Default)]
+pub(crate) struct HostState {
+    pub db: Option<rusqlite::Connection>,
+    pub sql_read_only: bool,
+    pub files: HashMap<String, Rc<RefCell<Vec<u8>>>>,
+    pub buffers: HashMap<u32, BufferData>,
+    pub next_handle: u32,
+    pub current_user: Option<CurrentUser>,
+    pub events: Vec<PublishedEvent>,
+    pub publish_failures: VecDeque<String>,
+    pub ffmpeg_results: VecDeque<Result<Vec<u8>, String>>,
+    pub ffmpeg_calls: Vec<RecordedFfmpegCall>,
+    pub http_results: VecDeque<Result<MockHttpResponse, String>>,
+    pub http_requests: Vec<RecordedHttpRequest>,
+}
+
+impl HostState {
+    /// 惰性打开内存 SQLite 连接
+    pub fn db(&mut self) -> Result<&rusqlite::Connection, String> {
+        if self.db.is_none() {
+            let conn = rusqlite::Connection::open_in_memory().map_err(|e| e.to_string())?;
+            self.db = Some(conn);
+        }
+        Ok(self.db.as_ref().expect("connection initialized above"))
+    }
+
+    /// 分配一个新的 Buffer 句柄
+    pub fn alloc_buffer(&mut self, data: BufferData) -> u32 {
+        self.next_handle += 1;
+        let handle = self.next_handle;
+        self.buffers.insert(handle, data);
+        handle
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<HostState> = RefCell::new(HostState::default());
+}
+
+/// 访问当前线程的 Mock 宿主状态
+///
+/// 每个测试线程拥有独立状态，`cargo test` 的并行执行互不干扰。
+pub(crate) fn with_state<R>(f: impl FnOnce(&mut HostState) -> R) -> R {
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// 同 `with_state`，但在线程局部存储已销毁时返回 `None`（用于析构路径）
+pub(crate) fn try_with_state<R>(f: impl FnOnce(&mut HostState) -> R) -> Option<R> {
+    STATE
+        .try_with(|state| state.try_borrow_mut().ok().map(|mut s| f(&mut s)))
+        .ok()
+        .flatten()
+}
+
+/// 重置当前线程的 Mock 宿主状态
+///
+/// 句柄计数保持递增，避免重置前遗留的 Buffer 与新 Buffer 句柄冲突；
+/// 旧状态在借用释放后再析构。
+pub(crate) fn reset() {
+    let old = STATE.with(|state| {
+        let next_handle = state.borrow().next_handle;
+        state.replace(HostState {
+            next_handle,
+            ..HostState::default()
+        })
+    });
+    drop(old);
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::db::{self, DbValue, ToDbValue};
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+fn setup() -> MockHost {
+    let host = MockHost::new();
+    host.execute_sql("CREATE TABLE tags (video_id INTEGER NOT NULL, tag TEXT NOT NULL UNIQUE)")
+        .unwrap();
+    host
+}
+
+fn row(video_id: i64, tag: &str) -> Vec<DbValue> {
+    vec![video_id.to_db_value(), tag.to_db_value()]
+}
+
+fn tag_count() -> i64 {
+    db::query_scalar("SELECT COUNT(*) FROM tags", &[]).unwrap()
+}
+
+const INSERT: &str = "INSERT INTO tags (video_id, tag) VALUES (?, ?)";
+
+#[test]
+fn returns_count_per_row() {
+    let _host = setup();
+
+    let counts = db::execute_batch(INSERT, [row(1, "rust"), row(1, "wasm")]).unwrap();
+    assert_eq!(counts, [1, 1]);
+
+    let counts = db::prepare("UPDATE tags SET video_id = ? WHERE tag = ?")
+        .execute_batch([row(2, "rust"), row(2, "missing")])
+        .unwrap();
+    assert_eq!(counts, [1, 0]);
+    assert!(!db::in_transaction());
+}
+
+#[test]
+fn empty_batch_is_a_no_op() {
+    let _host = setup();
+
+    assert!(db::execute_batch(INSERT, Vec::<Vec<DbValue>>::new())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn failure_reports_index_and_rolls_back() {
+    let _host = setup();
+
+    let err = db::execute_batch(INSERT, [row(1, "a"), row(1, "b"), row(1, "a"), row(1, "c")])
+        .unwrap_err();
+
+    assert_eq!(err.index, Some(2));
+    assert_eq!(err.error.status(), 409);
+    assert_eq!(tag_count(), 0);
+    assert!(!db::in_transaction());
+}
+
+#[test]
+fn converts_to_problem_with_batch_index() {
+    let _host = setup();
+
+    let err: VtxError = db::execute_batch(INSERT, [row(1, "a"), row(1, "a")])
+        .unwrap_err()
+        .into();
+
+    let problem = err.to_problem();
+    assert_eq!(problem.status, 409);
+    assert_eq!(problem.extensions["batch_index"], 1);
+}
+
+#[test]
+fn nested_batch_uses_savepoint() {
+    let _host = setup();
+
+    db::transaction(|tx| {
+        tx.execute(INSERT, &[&1, &"outer"])?;
+        let err = db::execute_batch(INSERT, [row(1, "inner"), row(1, "outer")]).unwrap_err();
+        assert_eq!(err.index, Some(1));
+        assert!(db::in_transaction());
+        Ok(())
+    })
+    .unwrap();
+
+    let tags: Vec<String> = db::query::<serde_json::Value>("SELECT tag FROM tags", &[])
+        .unwrap()
+        .into_iter()
+        .map(|row| row["tag"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(tags, ["outer"]);
+}
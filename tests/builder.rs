@@ -0,0 +1,164 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::db::{self, BuiltQuery, Order, TryToDbValue};
+
+fn params(q: &BuiltQuery) -> String {
+    format!("{:?}", q.params)
+}
+
+#[test]
+fn quotes_identifiers() {
+    let q = db::select("videos")
+        .columns(&["v.id", "we\"ird", "*"])
+        .build();
+    assert_eq!(q.sql, r#"SELECT "v"."id", "we""ird", * FROM "videos""#);
+
+    let q = db::select("odd table")
+        .column_raw("COUNT(*) AS total")
+        .where_eq("select", 1)
+        .build();
+    assert_eq!(
+        q.sql,
+        r#"SELECT COUNT(*) AS total FROM "odd table" WHERE "select" = ?"#
+    );
+}
+
+#[test]
+fn select_clauses_in_order() {
+    let q = db::select("videos")
+        .where_eq("owner_id", "u1")
+        .where_in("status", ["ready", "draft"])
+        .where_null("deleted_at")
+        .where_like("title", "%intro%")
+        .order_by("created_at", Order::Desc)
+        .order_by("id", Order::Asc)
+        .limit(20)
+        .offset(40)
+        .build();
+
+    assert_eq!(
+        q.sql,
+        r#"SELECT * FROM "videos" WHERE "owner_id" = ? AND "status" IN (?, ?) AND "deleted_at" IS NULL AND "title" LIKE ? ORDER BY "created_at" DESC, "id" ASC LIMIT 20 OFFSET 40"#
+    );
+    assert_eq!(
+        params(&q),
+        r#"[Text("u1"), Text("ready"), Text("draft"), Text("%intro%")]"#
+    );
+    assert!(q.check().is_ok());
+}
+
+#[test]
+fn limit_and_offset_combinations() {
+    assert_eq!(
+        db::select("v").limit(5).build().sql,
+        r#"SELECT * FROM "v" LIMIT 5"#
+    );
+    // SQLite 要求 OFFSET 前必须有 LIMIT
+    assert_eq!(
+        db::select("v").offset(10).build().sql,
+        r#"SELECT * FROM "v" LIMIT -1 OFFSET 10"#
+    );
+    assert_eq!(
+        db::select("v")
+            .where_in("id", Vec::<i64>::new())
+            .build()
+            .sql,
+        r#"SELECT * FROM "v" WHERE "id" IN ()"#
+    );
+}
+
+#[test]
+fn where_raw_keeps_param_order() {
+    let q = db::select("videos")
+        .where_eq("owner_id", "u1")
+        .where_raw("duration BETWEEN ? AND ?", &[&60, &600])
+        .where_raw("title <> '?' -- ?\n", &[])
+        .where_gt("views", 10)
+        .build();
+
+    assert_eq!(
+        q.sql,
+        "SELECT * FROM \"videos\" WHERE \"owner_id\" = ? AND (duration BETWEEN ? AND ?) \
+         AND (title <> '?' -- ?\n) AND \"views\" > ?"
+    );
+    assert_eq!(
+        params(&q),
+        r#"[Text("u1"), Integer(60), Integer(600), Integer(10)]"#
+    );
+    assert!(q.check().is_ok());
+}
+
+#[test]
+fn where_raw_rejects_placeholder_mismatch() {
+    let q = db::select("videos")
+        .where_raw("duration > ? AND views > ?", &[&60])
+        .build();
+    assert_eq!(q.check().unwrap_err().status(), 500);
+
+    let q = db::delete("videos").where_raw("id = 1", &[&1]).build();
+    assert!(q.check().is_err());
+}
+
+#[test]
+fn insert_values_and_returning() {
+    let q = db::insert("videos")
+        .value("title", "intro")
+        .value("duration", 90)
+        .value("note", None::<String>)
+        .returning(&["id", "created_at"])
+        .build();
+
+    assert_eq!(
+        q.sql,
+        r#"INSERT INTO "videos" ("title", "duration", "note") VALUES (?, ?, ?) RETURNING "id", "created_at""#
+    );
+    assert_eq!(params(&q), r#"[Text("intro"), Integer(90), NullVal]"#);
+
+    assert_eq!(
+        db::insert("videos").build().sql,
+        r#"INSERT INTO "videos" DEFAULT VALUES"#
+    );
+}
+
+#[test]
+fn update_params_follow_set_then_where() {
+    let q = db::update("videos")
+        .set("title", "renamed")
+        .set_raw("views = views + 1")
+        .set("duration", 30)
+        .where_eq("id", 7)
+        .where_not_null("published_at")
+        .returning(&["views"])
+        .build();
+
+    assert_eq!(
+        q.sql,
+        r#"UPDATE "videos" SET "title" = ?, views = views + 1, "duration" = ? WHERE "id" = ? AND "published_at" IS NOT NULL RETURNING "views""#
+    );
+    assert_eq!(params(&q), r#"[Text("renamed"), Integer(30), Integer(7)]"#);
+}
+
+#[test]
+fn delete_with_conditions_and_returning() {
+    let q = db::delete("videos")
+        .where_lt("expires_at", 100)
+        .where_ne("pinned", true)
+        .returning(&["id"])
+        .build();
+
+    assert_eq!(
+        q.sql,
+        r#"DELETE FROM "videos" WHERE "expires_at" < ? AND "pinned" <> ? RETURNING "id""#
+    );
+    assert_eq!(params(&q), r#"[Integer(100), Integer(1)]"#);
+    assert_eq!(db::delete("videos").build().sql, r#"DELETE FROM "videos""#);
+}
+
+#[test]
+fn conversion_errors_surface_on_check() {
+    let q = db::insert("videos").value("views", u64::MAX).build();
+    assert!(q.check().is_err());
+    // 出错的参数以 NULL 占位，占位符与参数数量保持一致
+    assert_eq!(q.params.len(), 1);
+    assert!(u64::MAX.try_to_db_value().is_err());
+}
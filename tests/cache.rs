@@ -0,0 +1,132 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::http::cache::{http_date, CacheControl, Precondition, Validators};
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+const MODIFIED: u64 = 1_700_000_000;
+
+fn request(method: &str, headers: &[(&str, &str)]) -> Request {
+    Request {
+        method: method.into(),
+        path: "/thumb".into(),
+        query: String::new(),
+        headers: headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        body: None,
+    }
+}
+
+fn validators() -> Validators {
+    Validators::new()
+        .version("v2")
+        .last_modified(MODIFIED)
+        .cache_control(CacheControl::new().public().max_age(3600))
+}
+
+#[test]
+fn if_none_match_returns_not_modified() {
+    let v = validators();
+
+    for header in [r#""v2""#, r#"W/"v2""#, r#""v1", "v2""#, "*"] {
+        let req = request("GET", &[("If-None-Match", header)]);
+        assert_eq!(v.evaluate(&req), Precondition::NotModified, "{}", header);
+    }
+
+    let resp = v.respond(&request("GET", &[("If-None-Match", r#""v2""#)]), || {
+        unreachable!("handler must not run on a cache hit")
+    });
+    assert_eq!(resp.status, 304);
+    assert_eq!(resp.header("ETag"), Some(r#""v2""#));
+    assert_eq!(resp.header("Cache-Control"), Some("public, max-age=3600"));
+    assert!(resp.body.is_none());
+}
+
+#[test]
+fn if_none_match_miss_or_unsafe_method() {
+    let v = validators();
+
+    let req = request("GET", &[("If-None-Match", r#""v1""#)]);
+    assert_eq!(v.evaluate(&req), Precondition::Modified);
+
+    let req = request("PUT", &[("If-None-Match", r#""v2""#)]);
+    assert_eq!(v.evaluate(&req), Precondition::Failed);
+    assert_eq!(v.respond(&req, || unreachable!()).status, 412);
+}
+
+#[test]
+fn if_none_match_takes_precedence_over_if_modified_since() {
+    let v = validators();
+    let req = request(
+        "GET",
+        &[
+            ("If-None-Match", r#""v1""#),
+            ("If-Modified-Since", &http_date(MODIFIED)),
+        ],
+    );
+    assert_eq!(v.evaluate(&req), Precondition::Modified);
+}
+
+#[test]
+fn if_modified_since_compares_dates() {
+    let v = validators();
+
+    let at = |secs: u64| request("GET", &[("If-Modified-Since", &http_date(secs))]);
+    assert_eq!(v.evaluate(&at(MODIFIED)), Precondition::NotModified);
+    assert_eq!(v.evaluate(&at(MODIFIED + 60)), Precondition::NotModified);
+    assert_eq!(v.evaluate(&at(MODIFIED - 1)), Precondition::Modified);
+
+    // 无法解析的日期与非安全方法均忽略该头
+    let req = request("GET", &[("If-Modified-Since", "yesterday")]);
+    assert_eq!(v.evaluate(&req), Precondition::Modified);
+    let req = request("POST", &[("If-Modified-Since", &http_date(MODIFIED))]);
+    assert_eq!(v.evaluate(&req), Precondition::Modified);
+}
+
+#[test]
+fn success_responses_get_validators() {
+    let v = validators();
+
+    let resp = v.respond(&request("GET", &[]), || ResponseBuilder::status(200));
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.header("ETag"), Some(r#""v2""#));
+    assert_eq!(
+        resp.header("Last-Modified"),
+        Some(http_date(MODIFIED).as_str())
+    );
+    assert_eq!(resp.header("Cache-Control"), Some("public, max-age=3600"));
+}
+
+#[test]
+fn error_responses_pass_through() {
+    let v = validators();
+
+    for status in [404, 500] {
+        let resp = v.respond(&request("GET", &[]), || ResponseBuilder::status(status));
+        assert_eq!(resp.status, status);
+        assert!(resp.headers.is_empty(), "{}", status);
+    }
+}
+
+#[test]
+fn json_cached_matches_own_etag() {
+    let _host = MockHost::new();
+    let data = serde_json::json!({"id": 1});
+
+    let first =
+        ResponseBuilder::json_cached(&request("GET", &[]), &data, CacheControl::new().no_cache())
+            .unwrap();
+    assert_eq!(first.status, 200);
+    let etag = first.header("ETag").unwrap().to_string();
+
+    let again = ResponseBuilder::json_cached(
+        &request("GET", &[("If-None-Match", &etag)]),
+        &data,
+        CacheControl::new().no_cache(),
+    )
+    .unwrap();
+    assert_eq!(again.status, 304);
+    assert_eq!(again.header("ETag"), Some(etag.as_str()));
+}
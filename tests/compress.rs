@@ -0,0 +1,128 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::http::compress::{negotiate, Compression, Encoding};
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+const ALL: [Encoding; 3] = Encoding::ALL;
+
+fn request(accept_encoding: Option<&str>) -> Request {
+    Request {
+        method: "GET".into(),
+        path: "/catalog".into(),
+        query: String::new(),
+        headers: accept_encoding
+            .map(|v| vec![("Accept-Encoding".to_string(), v.to_string())])
+            .unwrap_or_default(),
+        body: None,
+    }
+}
+
+fn json_response(len: usize) -> Response {
+    ResponseBuilder::new(200)
+        .header("Content-Type", "application/json")
+        .header("ETag", "\"v1\"")
+        .body(vec![b'a'; len])
+        .build()
+}
+
+#[test]
+fn negotiate_prefers_highest_q_value() {
+    assert_eq!(
+        negotiate("gzip;q=1.0, br;q=0.8", &ALL),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(
+        negotiate("gzip;q=0.2, zstd;q=0.9, br;q=0.5", &ALL),
+        Some(Encoding::Zstd)
+    );
+    // 权重相同时按候选顺序
+    assert_eq!(negotiate("gzip, zstd", &ALL), Some(Encoding::Zstd));
+    assert_eq!(
+        negotiate("gzip, zstd", &[Encoding::Gzip, Encoding::Zstd]),
+        Some(Encoding::Gzip)
+    );
+}
+
+#[test]
+fn negotiate_handles_refusals_wildcards_and_aliases() {
+    assert_eq!(negotiate("br;q=0, gzip;q=0", &ALL), None);
+    assert_eq!(negotiate("*", &ALL), Some(Encoding::Brotli));
+    assert_eq!(negotiate("*;q=0.5, br;q=0", &ALL), Some(Encoding::Zstd));
+    assert_eq!(negotiate("*;q=0", &ALL), None);
+    assert_eq!(negotiate("X-GZIP", &ALL), Some(Encoding::Gzip));
+    assert_eq!(negotiate(" Br ; Q=0.7 , ", &ALL), Some(Encoding::Brotli));
+    // 无法解析的权重忽略该项
+    assert_eq!(
+        negotiate("br;q=high, gzip;q=0.1", &ALL),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(negotiate("", &ALL), None);
+    assert_eq!(negotiate("br", &[Encoding::Gzip]), None);
+}
+
+#[test]
+fn without_accept_encoding_response_is_unchanged() {
+    let _host = MockHost::new();
+    let resp = Compression::new()
+        .min_size(0)
+        .apply(&request(None), json_response(4096));
+    assert_eq!(resp.header("Content-Encoding"), None);
+    assert_eq!(resp.header("ETag"), Some("\"v1\""));
+    assert_eq!(resp.header("Vary"), Some("Accept-Encoding"));
+}
+
+#[test]
+fn incompressible_media_types_are_skipped() {
+    let _host = MockHost::new();
+    let resp = ResponseBuilder::new(200)
+        .header("Content-Type", "video/mp4")
+        .body(vec![0u8; 4096])
+        .build();
+    let resp = Compression::new().apply(&request(Some("gzip")), resp);
+    assert_eq!(resp.header("Content-Encoding"), None);
+    assert_eq!(resp.header("Vary"), None);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn bodies_below_min_size_are_not_compressed() {
+    let _host = MockHost::new();
+    let gzip = Compression::new().encodings(&[Encoding::Gzip]);
+    let req = request(Some("gzip"));
+
+    let resp = gzip.apply(&req, json_response(1023));
+    assert_eq!(resp.header("Content-Encoding"), None);
+    assert_eq!(resp.header("Vary"), Some("Accept-Encoding"));
+
+    let resp = gzip.apply(&req, json_response(1024));
+    assert_eq!(resp.header("Content-Encoding"), Some("gzip"));
+
+    let resp = gzip.clone().min_size(4096).apply(&req, json_response(2048));
+    assert_eq!(resp.header("Content-Encoding"), None);
+
+    let resp = gzip.max_size(512).apply(&req, json_response(1024));
+    assert_eq!(resp.header("Content-Encoding"), None);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_round_trip_weakens_etag() {
+    use std::io::Read;
+
+    let _host = MockHost::new();
+    let resp = Compression::new()
+        .encodings(&[Encoding::Gzip])
+        .apply(&request(Some("br;q=0, gzip")), json_response(4096));
+    assert_eq!(resp.header("Content-Encoding"), Some("gzip"));
+    assert_eq!(resp.header("ETag"), Some("W/\"v1\""));
+    assert_eq!(resp.header("Content-Length"), None);
+
+    let compressed = resp.body.unwrap().try_read_all().unwrap();
+    assert!(compressed.len() < 4096);
+    let mut plain = Vec::new();
+    flate2::read::GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut plain)
+        .unwrap();
+    assert_eq!(plain, vec![b'a'; 4096]);
+}
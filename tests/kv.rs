@@ -0,0 +1,173 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+use vtx_sdk::kv;
+use vtx_sdk::migrations::Migration;
+use vtx_sdk::testing::MockHost;
+
+/// 将键的过期时间改为 1 秒前（TTL 按数据库时钟计算，测试中无法等待）
+fn expire(host: &MockHost, key: &str) {
+    host.execute_sql(&format!(
+        "UPDATE _vtx_kv SET expires_at = CAST(strftime('%s', 'now') AS INTEGER) - 1 WHERE key = '{}'",
+        key
+    ))
+    .unwrap();
+}
+
+#[test]
+fn reads_on_missing_table_run_no_ddl() {
+    let host = MockHost::new();
+    host.set_sql_read_only(true);
+
+    assert_eq!(kv::get::<String>("x").unwrap(), None);
+    assert!(kv::list_prefix::<String>("").unwrap().is_empty());
+    // 删除是写操作，只读策略下被拒绝
+    assert!(kv::delete("x").is_err());
+    assert!(kv::set("x", &1).is_err());
+
+    // 条件写入与清理在表不存在时同样不建表
+    host.set_sql_read_only(false);
+    assert!(!kv::delete("x").unwrap());
+    assert!(!kv::set_if_version("x", &1, 1).unwrap());
+    assert_eq!(kv::purge_expired().unwrap(), 0);
+    let tables: i64 = vtx_sdk::db::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name = ?",
+        &[&kv::TABLE],
+    )
+    .unwrap();
+    assert_eq!(tables, 0);
+
+    kv::set("x", &1).unwrap();
+    assert_eq!(kv::get::<i32>("x").unwrap(), Some(1));
+}
+
+#[test]
+fn migration_creates_table() {
+    static MIGRATIONS: &[Migration] = &[kv::migration(1)];
+
+    let host = MockHost::new();
+    host.check_migrations(MIGRATIONS).unwrap();
+    host.set_sql_read_only(true);
+    assert_eq!(kv::get::<String>("x").unwrap(), None);
+}
+
+#[test]
+fn ttl_expiry_hides_keys() {
+    let host = MockHost::new();
+    kv::set_with_ttl("session:a", &"u1", Duration::from_secs(60)).unwrap();
+    kv::set_with_ttl("session:b", &"u2", Duration::from_millis(1)).unwrap();
+    kv::set("session:c", &"u3").unwrap();
+    assert_eq!(
+        kv::get::<String>("session:b").unwrap().as_deref(),
+        Some("u2")
+    );
+
+    expire(&host, "session:b");
+
+    assert_eq!(kv::get::<String>("session:b").unwrap(), None);
+    let keys: Vec<String> = kv::list_prefix::<String>("session:")
+        .unwrap()
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys, ["session:a", "session:c"]);
+    assert!(!kv::set_if_version("session:b", &"u9", 1).unwrap());
+
+    assert_eq!(kv::purge_expired().unwrap(), 1);
+    assert_eq!(kv::purge_expired().unwrap(), 0);
+}
+
+#[test]
+fn set_clears_ttl() {
+    let host = MockHost::new();
+    kv::set_with_ttl("k", &1, Duration::from_secs(60)).unwrap();
+    kv::set("k", &2).unwrap();
+    expire(&host, "k");
+    assert_eq!(kv::get::<i32>("k").unwrap(), None);
+
+    kv::set_with_ttl("k", &1, Duration::from_secs(60)).unwrap();
+    kv::set("k", &2).unwrap();
+    assert_eq!(kv::purge_expired().unwrap(), 0);
+    assert_eq!(kv::get::<i32>("k").unwrap(), Some(2));
+}
+
+#[test]
+fn compare_and_swap_detects_version_races() {
+    let _host = MockHost::new();
+
+    assert!(kv::compare_and_swap("counter", None, &1).unwrap());
+    // 键已存在时 `None` 期望失败
+    assert!(!kv::compare_and_swap("counter", None, &5).unwrap());
+    assert!(!kv::compare_and_swap("counter", Some(&2), &3).unwrap());
+    assert!(kv::compare_and_swap("counter", Some(&1), &2).unwrap());
+
+    // 实例 A 读取后，实例 B 抢先写入：A 基于旧版本号的写入失败
+    let seen = kv::get_versioned::<i32>("counter").unwrap().unwrap();
+    assert_eq!(seen.value, 2);
+    kv::set("counter", &10).unwrap();
+    assert!(!kv::set_if_version("counter", &(seen.value + 1), seen.version).unwrap());
+
+    let latest = kv::get_versioned::<i32>("counter").unwrap().unwrap();
+    assert_eq!(latest.version, seen.version + 1);
+    assert!(kv::set_if_version("counter", &11, latest.version).unwrap());
+    assert_eq!(kv::get::<i32>("counter").unwrap(), Some(11));
+}
+
+#[test]
+fn compare_and_swap_replaces_expired_key() {
+    let host = MockHost::new();
+    kv::set_with_ttl("lock", &"a", Duration::from_secs(60)).unwrap();
+    assert!(!kv::compare_and_swap("lock", None, &"b").unwrap());
+
+    expire(&host, "lock");
+    assert!(!kv::compare_and_swap("lock", Some(&"a"), &"b").unwrap());
+    assert!(kv::compare_and_swap("lock", None, &"b").unwrap());
+    assert_eq!(kv::get::<String>("lock").unwrap().as_deref(), Some("b"));
+}
+
+#[test]
+fn compare_and_swap_compares_json_structurally() {
+    let _host = MockHost::new();
+    kv::set("cfg", &serde_json::json!({"a": 1, "b": 2})).unwrap();
+
+    let expected = serde_json::json!({"b": 2, "a": 1});
+    assert!(kv::compare_and_swap("cfg", Some(&expected), &serde_json::json!({"a": 3})).unwrap());
+}
+
+#[test]
+fn delete_reports_whether_live_key_existed() {
+    let host = MockHost::new();
+    kv::set("a", &1).unwrap();
+    kv::set_with_ttl("b", &2, Duration::from_secs(60)).unwrap();
+    expire(&host, "b");
+
+    assert!(kv::delete("a").unwrap());
+    assert!(!kv::delete("a").unwrap());
+    assert!(!kv::delete("missing").unwrap());
+    // 已过期的键被删除，但视为不存在
+    assert!(!kv::delete("b").unwrap());
+    assert_eq!(kv::purge_expired().unwrap(), 0);
+}
+
+#[test]
+fn list_prefix_matches_literal_prefix() {
+    let _host = MockHost::new();
+    for key in ["user:2", "user:1", "user_x", "users", "视频:1", "视频库"] {
+        kv::set(key, &key).unwrap();
+    }
+
+    let keys = |prefix: &str| -> Vec<String> {
+        kv::list_prefix::<String>(prefix)
+            .unwrap()
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect()
+    };
+
+    assert_eq!(keys("user:"), ["user:1", "user:2"]);
+    // `_` / `%` 不是通配符
+    assert_eq!(keys("user_"), ["user_x"]);
+    assert_eq!(keys("视频:"), ["视频:1"]);
+    assert_eq!(keys("").len(), 6);
+    assert!(keys("none").is_empty());
+}
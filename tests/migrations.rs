@@ -0,0 +1,48 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::migrations::{self, Migration};
+use vtx_sdk::testing::MockHost;
+
+static MIGRATIONS: &[Migration] = &[
+    Migration::new(
+        1,
+        "create_videos",
+        "CREATE TABLE videos (id TEXT PRIMARY KEY)",
+    ),
+    Migration::new(
+        2,
+        "add_views",
+        "ALTER TABLE videos ADD COLUMN views INTEGER",
+    ),
+];
+
+#[test]
+fn valid_migrations_convert_to_host_scripts() {
+    let scripts = migrations::to_host_scripts(MIGRATIONS);
+    assert_eq!(scripts.len(), 2);
+    assert!(scripts[1].contains(migrations::HISTORY_TABLE));
+
+    let host = MockHost::new();
+    host.run_migrations(MIGRATIONS).unwrap();
+    assert_eq!(
+        migrations::verify_applied(MIGRATIONS).unwrap(),
+        Vec::<u32>::new()
+    );
+}
+
+#[test]
+fn invalid_migrations_are_rejected() {
+    let duplicate = [MIGRATIONS[0], MIGRATIONS[0]];
+    let empty = [Migration::new(1, "noop", "  \n")];
+
+    for list in [&duplicate[..], &empty[..]] {
+        assert!(migrations::try_to_host_scripts(list).is_err());
+        assert!(MockHost::new().run_migrations(list).is_err());
+    }
+}
+
+#[test]
+#[should_panic(expected = "strictly increasing")]
+fn to_host_scripts_fails_loudly_on_unordered_versions() {
+    migrations::to_host_scripts(&[MIGRATIONS[1], MIGRATIONS[0]]);
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "testing")]
+
+use std::io::Read;
+use vtx_sdk::http::multipart::Multipart;
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+const BOUNDARY: &str = "----vtxBoundary7MA4YWxk";
+
+/// 按 `(头部, 数据)` 拼接 multipart 请求体
+fn body(parts: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut out = b"preamble is ignored\r\n".to_vec();
+    for (headers, data) in parts {
+        out.extend_from_slice(format!("--{}\r\n{}\r\n\r\n", BOUNDARY, headers).as_bytes());
+        out.extend_from_slice(data);
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(format!("--{}--\r\nepilogue is ignored", BOUNDARY).as_bytes());
+    out
+}
+
+fn field(name: &str) -> String {
+    format!("Content-Disposition: form-data; name=\"{}\"", name)
+}
+
+fn request(body: &[u8]) -> Request {
+    Request {
+        method: "POST".into(),
+        path: "/upload".into(),
+        query: String::new(),
+        headers: vec![(
+            "Content-Type".into(),
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )],
+        body: Some(stream::memory_buffer(body)),
+    }
+}
+
+/// 大于解析器单次读取块（64 KiB）且包含分隔符前缀的文件内容
+fn tricky_payload(len: usize) -> Vec<u8> {
+    let near_miss = format!("\r\n--{}!", &BOUNDARY[..BOUNDARY.len() - 1]);
+    let mut data = Vec::with_capacity(len);
+    let mut i = 0u32;
+    while data.len() < len {
+        if i.is_multiple_of(997) {
+            data.extend_from_slice(near_miss.as_bytes());
+        }
+        data.push((i % 251) as u8);
+        i += 1;
+    }
+    data.truncate(len);
+    data
+}
+
+#[test]
+fn parses_fields_and_files() {
+    let _host = MockHost::new();
+    let file =
+        "Content-Disposition: form-data; name=\"video\"; filename=\"C:\\\\clips\\\\a.mp4\"\r\n\
+                Content-Type: video/mp4";
+    let req = request(&body(&[
+        (&field("title"), "Intro 你好".as_bytes()),
+        (file, b"\x00\x01binary\r\n"),
+        (
+            "Content-Disposition: form-data; name=\"doc\"; filename=\"x.txt\"; \
+             filename*=UTF-8''%E6%96%87%E6%A1%A3.txt",
+            b"",
+        ),
+    ]));
+
+    let mut multipart = req.multipart().unwrap();
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    assert_eq!(part.name(), Some("title"));
+    assert_eq!(part.filename(), None);
+    assert_eq!(part.text().unwrap(), "Intro 你好");
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    assert_eq!(part.name(), Some("video"));
+    assert_eq!(part.filename(), Some("a.mp4"));
+    assert_eq!(part.content_type(), Some("video/mp4"));
+    assert_eq!(part.read_all().unwrap(), b"\x00\x01binary\r\n");
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    assert_eq!(part.filename(), Some("文档.txt"));
+    assert!(part.read_all().unwrap().is_empty());
+
+    assert!(multipart.next_part().unwrap().is_none());
+    assert!(multipart.next_part().unwrap().is_none());
+}
+
+#[test]
+fn streams_large_parts_across_chunks() {
+    let _host = MockHost::new();
+    let payload = tricky_payload(300 * 1024);
+    let req = request(&body(&[
+        (&field("file"), &payload),
+        (&field("after"), b"tail"),
+    ]));
+
+    let mut multipart = req.multipart().unwrap();
+
+    // 用很小的缓冲区读取，验证跨块边界与分隔符前缀的处理
+    let mut part = multipart.next_part().unwrap().unwrap();
+    let mut read = Vec::new();
+    let mut buf = [0u8; 7];
+    loop {
+        let n = part.read_chunk(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        read.extend_from_slice(&buf[..n]);
+    }
+    assert!(read == payload);
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    assert_eq!(part.text().unwrap(), "tail");
+    assert!(multipart.next_part().unwrap().is_none());
+}
+
+#[test]
+fn skips_unread_parts() {
+    let _host = MockHost::new();
+    let payload = tricky_payload(200 * 1024);
+    let req = request(&body(&[
+        (&field("skipped"), &payload),
+        (&field("partly"), &payload),
+        (&field("kept"), b"value"),
+    ]));
+
+    let mut multipart = req.multipart().unwrap();
+    assert_eq!(
+        multipart.next_part().unwrap().unwrap().name(),
+        Some("skipped")
+    );
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    let mut head = [0u8; 10];
+    part.read_exact(&mut head).unwrap();
+    assert_eq!(head, payload[..10]);
+
+    let mut part = multipart.next_part().unwrap().unwrap();
+    assert_eq!(part.name(), Some("kept"));
+    assert_eq!(part.text().unwrap(), "value");
+    assert!(multipart.next_part().unwrap().is_none());
+}
+
+#[test]
+fn copies_part_to_buffer() {
+    let _host = MockHost::new();
+    let payload = tricky_payload(150 * 1024);
+    let req = request(&body(&[(&field("file"), &payload)]));
+
+    let output = stream::memory_buffer([]);
+    let mut multipart = req.multipart().unwrap();
+    let copied = multipart
+        .next_part()
+        .unwrap()
+        .unwrap()
+        .copy_to(&output)
+        .unwrap();
+
+    assert_eq!(copied, payload.len() as u64);
+    assert!(output.try_read_all().unwrap() == payload);
+}
+
+#[test]
+fn enforces_limits() {
+    let _host = MockHost::new();
+    let req = request(&body(&[
+        (&field("a"), &[b'x'; 2048]),
+        (&field("b"), b""),
+        (&field("c"), b""),
+    ]));
+
+    let mut multipart = req.multipart().unwrap().max_part_size(1024);
+    let err = multipart
+        .next_part()
+        .unwrap()
+        .unwrap()
+        .read_all()
+        .unwrap_err();
+    assert_eq!(err.status(), 413);
+
+    let mut multipart = req.multipart().unwrap().max_parts(2);
+    assert!(multipart.next_part().unwrap().is_some());
+    assert!(multipart.next_part().unwrap().is_some());
+    assert_eq!(multipart.next_part().err().unwrap().status(), 413);
+
+    let long_header = format!("{}\r\nX-Pad: {}", field("a"), "p".repeat(512));
+    let req = request(&body(&[(&long_header, b"")]));
+    let mut multipart = req.multipart().unwrap().max_header_size(256);
+    assert_eq!(multipart.next_part().err().unwrap().status(), 413);
+}
+
+#[test]
+fn rejects_malformed_bodies() {
+    let _host = MockHost::new();
+
+    let mut truncated = body(&[(&field("a"), b"value")]);
+    truncated.truncate(truncated.len() - 30);
+    let req = request(&truncated);
+    let mut multipart = req.multipart().unwrap();
+    let err = multipart
+        .next_part()
+        .unwrap()
+        .unwrap()
+        .read_all()
+        .unwrap_err();
+    assert_eq!(err.status(), 400);
+
+    let mut req = request(&body(&[]));
+    req.headers = vec![("Content-Type".into(), "application/json".into())];
+    assert_eq!(req.multipart().err().unwrap().status(), 400);
+
+    req.body = None;
+    assert_eq!(req.multipart().err().unwrap().status(), 400);
+}
+
+#[test]
+fn parses_stored_buffer_with_explicit_boundary() {
+    let _host = MockHost::new();
+    let buffer = stream::memory_buffer(body(&[(&field("a"), b"1")]));
+
+    let mut multipart = Multipart::new(&buffer, BOUNDARY);
+    let mut part = multipart.next_part().unwrap().unwrap();
+    let mut text = String::new();
+    part.read_to_string(&mut text).unwrap();
+    assert_eq!(text, "1");
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::db::{self, NamedParams};
+use vtx_sdk::testing::MockHost;
+
+fn rewrite(params: &NamedParams, sql: &str) -> (String, String) {
+    let (sql, values) = params.rewrite(sql).unwrap();
+    (sql, format!("{:?}", values))
+}
+
+#[test]
+fn placeholders_in_literals_and_comments_are_kept() {
+    let params = db::params! { "id" => 7 };
+    let (sql, values) = rewrite(
+        &params,
+        "SELECT ':id', \":id\", `:id`, [:id] FROM v -- :id\n\
+         WHERE /* @id :id */ id = :id AND note <> 'it''s :id'",
+    );
+    assert_eq!(
+        sql,
+        "SELECT ':id', \":id\", `:id`, [:id] FROM v -- :id\n\
+         WHERE /* @id :id */ id = ? AND note <> 'it''s :id'"
+    );
+    assert_eq!(values, "[Integer(7)]");
+}
+
+#[test]
+fn repeated_names_expand_in_order() {
+    let params = db::params! { ":owner" => "u1", "@id" => 7, "owner" => "u2" };
+    let (sql, values) = rewrite(
+        &params,
+        "SELECT * FROM v WHERE owner_id = :owner AND (id = @id OR parent_id = :id) AND editor = :owner",
+    );
+    assert_eq!(
+        sql,
+        "SELECT * FROM v WHERE owner_id = ? AND (id = ? OR parent_id = ?) AND editor = ?"
+    );
+    // 重复绑定同名参数时以最后一次为准
+    assert_eq!(
+        values,
+        r#"[Text("u2"), Integer(7), Integer(7), Text("u2")]"#
+    );
+}
+
+#[test]
+fn missing_and_unused_names_are_errors() {
+    let err = db::params! { "id" => 7 }
+        .rewrite("SELECT * FROM v WHERE id = :id AND owner_id = :owner")
+        .unwrap_err();
+    assert!(err.to_string().contains(":owner"), "{}", err);
+
+    let err = db::params! { "id" => 7, "title" => "x" }
+        .rewrite("SELECT * FROM v WHERE id = :id")
+        .unwrap_err();
+    assert!(err.to_string().contains("title"), "{}", err);
+
+    assert!(db::params! { "id" => 7 }
+        .rewrite("SELECT * FROM v WHERE id = :id AND owner_id = ?")
+        .is_err());
+    assert!(db::params! { "n" => u64::MAX }
+        .rewrite("SELECT :n")
+        .is_err());
+}
+
+#[test]
+fn double_colon_casts_are_not_parameters() {
+    let params = db::params! { "id" => 7 };
+    let (sql, values) = rewrite(&params, "SELECT :id::text, created_at::date FROM v");
+    assert_eq!(sql, "SELECT ?::text, created_at::date FROM v");
+    assert_eq!(values, "[Integer(7)]");
+
+    let (sql, _) = rewrite(&NamedParams::new(), "SELECT a:::b, 'x'");
+    assert_eq!(sql, "SELECT a:::b, 'x'");
+}
+
+#[test]
+fn named_queries_run_against_host() {
+    let _host = MockHost::new();
+    db::execute("CREATE TABLE v (id INTEGER, title TEXT)", &[]).unwrap();
+    db::execute_named(
+        "INSERT INTO v (id, title) VALUES (:id, :title)",
+        &db::params! { "id" => 1, "title" => "it's :id" },
+    )
+    .unwrap();
+
+    let title: String = db::query_scalar_named(
+        "SELECT title FROM v WHERE id = :id AND title <> ':id'",
+        &db::params! { "id" => 1 },
+    )
+    .unwrap();
+    assert_eq!(title, "it's :id");
+}
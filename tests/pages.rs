@@ -0,0 +1,123 @@
+#![cfg(feature = "testing")]
+
+use serde::Deserialize;
+use vtx_sdk::db::{self, Order};
+use vtx_sdk::testing::MockHost;
+
+#[derive(Debug, Deserialize)]
+struct Video {
+    id: i64,
+}
+
+fn setup(count: i64) -> MockHost {
+    let host = MockHost::new();
+    host.execute_sql("CREATE TABLE videos (id INTEGER PRIMARY KEY, public INTEGER NOT NULL)")
+        .unwrap();
+    for id in 1..=count {
+        db::execute(
+            "INSERT INTO videos (id, public) VALUES (?, ?)",
+            &[&id, &(id % 2 == 1)],
+        )
+        .unwrap();
+    }
+    host
+}
+
+fn page_ids(pages: db::Pages<Video>) -> Vec<Vec<i64>> {
+    pages
+        .map(|page| page.unwrap().into_iter().map(|v| v.id).collect())
+        .collect()
+}
+
+#[test]
+fn offset_pages_until_short_page() {
+    let _host = setup(7);
+
+    let pages = db::query_pages("SELECT id FROM videos ORDER BY id", &[], 3);
+    assert_eq!(page_ids(pages), [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+}
+
+#[test]
+fn offset_pages_stop_on_empty_page() {
+    let _host = setup(6);
+
+    let pages = db::query_pages("SELECT id FROM videos ORDER BY id;", &[], 3);
+    assert_eq!(page_ids(pages), [vec![1, 2, 3], vec![4, 5, 6]]);
+}
+
+#[test]
+fn empty_result_yields_no_pages() {
+    let _host = setup(0);
+
+    let mut pages = db::query_pages::<Video>("SELECT id FROM videos ORDER BY id", &[], 3);
+    assert!(pages.next().is_none());
+}
+
+#[test]
+fn keyset_pages_keep_query_params() {
+    let _host = setup(9);
+
+    let pages = db::query_pages("SELECT id FROM videos WHERE public = ?", &[&true], 2)
+        .keyset("id", Order::Asc);
+    assert_eq!(page_ids(pages), [vec![1, 3], vec![5, 7], vec![9]]);
+
+    let pages = db::query_pages("SELECT id FROM videos WHERE public = ?", &[&true], 2)
+        .keyset("id", Order::Desc);
+    assert_eq!(page_ids(pages), [vec![9, 7], vec![5, 3], vec![1]]);
+}
+
+#[test]
+fn keyset_skips_rows_deleted_between_pages() {
+    let _host = setup(6);
+
+    let mut pages =
+        db::query_pages::<Video>("SELECT id FROM videos", &[], 2).keyset("id", Order::Asc);
+    assert_eq!(pages.next().unwrap().unwrap().len(), 2);
+
+    // 偏移分页在删除已读行后会跳过未读行，键集分页不受影响
+    db::execute("DELETE FROM videos WHERE id <= 2", &[]).unwrap();
+    let rest: Vec<i64> = pages.rows().map(|v| v.unwrap().id).collect();
+    assert_eq!(rest, [3, 4, 5, 6]);
+}
+
+#[test]
+fn missing_keyset_column_fails_once() {
+    let _host = setup(3);
+
+    let mut pages =
+        db::query_pages::<Video>("SELECT id FROM videos", &[], 1).keyset("title", Order::Asc);
+    assert!(pages.next().unwrap().is_err());
+    assert!(pages.next().is_none());
+}
+
+#[test]
+fn query_one_and_optional_detect_row_count() {
+    let _host = setup(3);
+
+    let video: Video = db::query_one("SELECT id FROM videos WHERE id = ?;", &[&2]).unwrap();
+    assert_eq!(video.id, 2);
+    let none: Option<Video> =
+        db::query_optional("SELECT id FROM videos WHERE id = 9", &[]).unwrap();
+    assert!(none.is_none());
+    assert_eq!(
+        db::query_one::<Video>("SELECT id FROM videos WHERE id = 9", &[])
+            .err()
+            .unwrap()
+            .status(),
+        404
+    );
+
+    // 多行时报错，而不是静默取第一行
+    assert!(db::query_one::<Video>("SELECT id FROM videos", &[]).is_err());
+    assert!(db::query_optional::<Video>("SELECT id FROM videos", &[]).is_err());
+    assert!(db::query_scalar::<i64>("SELECT id FROM videos", &[]).is_err());
+
+    let first: Video = db::query_one(
+        "WITH ranked AS (SELECT id FROM videos ORDER BY id DESC LIMIT 1) SELECT id FROM ranked",
+        &[],
+    )
+    .unwrap();
+    assert_eq!(first.id, 3);
+    let total: i64 = db::query_scalar("SELECT COUNT(*) FROM videos", &[]).unwrap();
+    assert_eq!(total, 3);
+}
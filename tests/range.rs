@@ -0,0 +1,158 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::http::range::{parse_range, ByteRange, FileResponse, RangeRequest, MAX_CHUNK_LIMIT};
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+fn partial(start: u64, end: u64) -> RangeRequest {
+    RangeRequest::Partial(ByteRange::new(start, end))
+}
+
+fn request(headers: &[(&str, &str)]) -> Request {
+    Request {
+        method: "GET".into(),
+        path: "/video".into(),
+        query: String::new(),
+        headers: headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        body: None,
+    }
+}
+
+fn file(host: &MockHost, len: usize) -> Vec<u8> {
+    let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+    host.add_file("f1", data.clone());
+    data
+}
+
+#[test]
+fn parses_bounded_and_open_ranges() {
+    assert_eq!(parse_range("bytes=0-99", 1000), partial(0, 99));
+    assert_eq!(parse_range(" bytes=10 - 10 ", 1000), partial(10, 10));
+    assert_eq!(parse_range("bytes=500-", 1000), partial(500, 999));
+    // 末尾超出文件大小时截断到最后一个字节
+    assert_eq!(parse_range("bytes=900-5000", 1000), partial(900, 999));
+}
+
+#[test]
+fn parses_suffix_ranges() {
+    assert_eq!(parse_range("bytes=-100", 1000), partial(900, 999));
+    assert_eq!(parse_range("bytes=-5000", 1000), partial(0, 999));
+    assert_eq!(parse_range("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+    assert_eq!(parse_range("bytes=-10", 0), RangeRequest::Unsatisfiable);
+}
+
+#[test]
+fn unsatisfiable_ranges() {
+    assert_eq!(
+        parse_range("bytes=1000-", 1000),
+        RangeRequest::Unsatisfiable
+    );
+    assert_eq!(
+        parse_range("bytes=2000-3000", 1000),
+        RangeRequest::Unsatisfiable
+    );
+}
+
+#[test]
+fn invalid_or_multi_ranges_fall_back_to_full() {
+    for header in [
+        "bytes=0-10,20-30",
+        "bytes=5-1",
+        "bytes=-",
+        "bytes=a-b",
+        "bytes=0-b",
+        "bytes=+1-2",
+        "bytes 0-1",
+        "items=0-1",
+        "",
+    ] {
+        assert_eq!(parse_range(header, 1000), RangeRequest::Full, "{}", header);
+    }
+}
+
+#[test]
+fn byte_range_len() {
+    assert_eq!(ByteRange::new(0, 99).len(), 100);
+    assert_eq!(ByteRange::new(5, 4).len(), 0);
+    assert!(ByteRange::new(5, 4).is_empty());
+    assert_eq!(ByteRange::new(0, u64::MAX).len(), u64::MAX);
+    assert_eq!(ByteRange::new(0, 99).content_range(1000), "bytes 0-99/1000");
+}
+
+#[test]
+fn responds_with_206_and_content_range() {
+    let host = MockHost::new();
+    let data = file(&host, 1000);
+
+    let resp = FileResponse::new("f1")
+        .content_type("video/mp4")
+        .respond(&request(&[("Range", "bytes=100-199")]));
+    assert_eq!(resp.status, 206);
+    assert_eq!(resp.header("Content-Range"), Some("bytes 100-199/1000"));
+    assert_eq!(resp.header("Content-Length"), Some("100"));
+    assert_eq!(resp.header("Content-Type"), Some("video/mp4"));
+    assert!(resp.body.unwrap().try_read_all().unwrap() == data[100..200]);
+
+    let resp = FileResponse::new("f1").respond(&request(&[]));
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.header("Accept-Ranges"), Some("bytes"));
+    assert!(resp.body.unwrap().try_read_all().unwrap() == data);
+}
+
+#[test]
+fn responds_with_416_for_unsatisfiable_range() {
+    let host = MockHost::new();
+    file(&host, 1000);
+
+    let resp = FileResponse::new("f1").respond(&request(&[("Range", "bytes=1000-")]));
+    assert_eq!(resp.status, 416);
+    assert_eq!(resp.header("Content-Range"), Some("bytes */1000"));
+    assert!(resp.body.is_none());
+}
+
+#[test]
+fn if_range_mismatch_returns_full_content() {
+    let host = MockHost::new();
+    file(&host, 1000);
+
+    let respond = |if_range: &str| {
+        FileResponse::new("f1")
+            .etag("\"v2\"")
+            .respond(&request(&[("Range", "bytes=0-9"), ("If-Range", if_range)]))
+            .status
+    };
+    assert_eq!(respond("\"v2\""), 206);
+    assert_eq!(respond("\"v1\""), 200);
+    assert_eq!(respond("W/\"v2\""), 200);
+}
+
+#[test]
+fn caps_open_ranges_at_max_chunk() {
+    let host = MockHost::new();
+    file(&host, 1000);
+
+    let resp = FileResponse::new("f1")
+        .max_chunk(300)
+        .respond(&request(&[("Range", "bytes=0-")]));
+    assert_eq!(resp.status, 206);
+    assert_eq!(resp.header("Content-Range"), Some("bytes 0-299/1000"));
+}
+
+#[test]
+fn unlimited_max_chunk_still_has_upper_bound() {
+    let host = MockHost::new();
+    let len = MAX_CHUNK_LIMIT as usize + 1024;
+    host.add_file("big", vec![0u8; len]);
+
+    let resp = FileResponse::new("big")
+        .max_chunk(0)
+        .respond(&request(&[("Range", "bytes=0-")]));
+    assert_eq!(resp.status, 206);
+    assert_eq!(
+        resp.header("Content-Range"),
+        Some(format!("bytes 0-{}/{}", MAX_CHUNK_LIMIT - 1, len).as_str())
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "testing")]
+
+use std::collections::BTreeMap;
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+/// 键不是字符串的映射无法序列化为 JSON
+fn unserializable() -> BTreeMap<(i32, i32), i32> {
+    BTreeMap::from([((1, 2), 3)])
+}
+
+#[test]
+fn json_serialization_failure_is_a_server_error() {
+    let _host = MockHost::new();
+
+    let err = ResponseBuilder::try_json(&unserializable()).err().unwrap();
+    assert_eq!(err.status(), 500);
+    assert_eq!(err.code(), "serialization_error");
+
+    let resp = ResponseBuilder::json(&unserializable());
+    assert_eq!(resp.status, 500);
+}
+
+#[test]
+fn query_decode_failure_is_a_server_error() {
+    let host = MockHost::new();
+    host.execute_sql("CREATE TABLE videos (id INTEGER PRIMARY KEY, title TEXT)")
+        .unwrap();
+    host.execute_sql("INSERT INTO videos (title) VALUES ('intro')")
+        .unwrap();
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Video {
+        #[allow(dead_code)]
+        title: i64,
+    }
+
+    let err = db::query::<Video>("SELECT title FROM videos", &[]).unwrap_err();
+    assert_eq!(err.status(), 500);
+
+    let err = db::query_scalar::<i64>("SELECT title FROM videos", &[]).unwrap_err();
+    assert_eq!(err.status(), 500);
+
+    let row = db::query_as::<db::Row>("SELECT title FROM videos", &[]).unwrap();
+    assert_eq!(row[0].get::<i64>("title").unwrap_err().status(), 500);
+    assert_eq!(row[0].get::<String>("missing").unwrap_err().status(), 500);
+}
@@ -0,0 +1,267 @@
+#![cfg(feature = "testing")]
+
+use serde::Deserialize;
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+fn request(method: &str, target: &str) -> Request {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    Request {
+        method: method.into(),
+        path: path.into(),
+        query: query.into(),
+        headers: Vec::new(),
+        body: None,
+    }
+}
+
+fn text(resp: Response) -> String {
+    String::from_utf8(resp.body.expect("response body").try_read_all().unwrap()).unwrap()
+}
+
+fn get(router: &Router, target: &str) -> VtxResult<Response> {
+    router.dispatch(request("GET", target))
+}
+
+#[derive(Debug, Deserialize)]
+struct Paging {
+    page: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiKey {
+    #[serde(rename = "x-api-key")]
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewVideo {
+    title: String,
+}
+
+#[test]
+fn matches_params_and_wildcards() {
+    let _host = MockHost::new();
+    let router = Router::new()
+        .get("/videos/:id", |Path(id): Path<i64>| {
+            Ok(ResponseBuilder::text(format!("video {}", id)))
+        })
+        .get(
+            "/users/:user/videos/:id",
+            |Path((user, id)): Path<(String, u32)>| {
+                Ok(ResponseBuilder::text(format!("{} {}", user, id)))
+            },
+        )
+        .get("/files/*rest", |req: &Request, params: &Params| {
+            assert_eq!(req.path, "/files/a%20b/c.mp4");
+            Ok(ResponseBuilder::text(params.require("rest")?))
+        });
+
+    assert_eq!(text(get(&router, "/videos/42").unwrap()), "video 42");
+    assert_eq!(text(get(&router, "//videos/42/").unwrap()), "video 42");
+    assert_eq!(
+        text(get(&router, "/users/%E5%BC%A0/videos/7").unwrap()),
+        "张 7"
+    );
+    assert_eq!(
+        text(get(&router, "/files/a%20b/c.mp4").unwrap()),
+        "a b/c.mp4"
+    );
+
+    assert_eq!(get(&router, "/videos").unwrap().status, 404);
+    assert_eq!(get(&router, "/videos/42/extra").unwrap().status, 404);
+}
+
+#[test]
+fn static_segments_outrank_params_and_wildcards() {
+    let _host = MockHost::new();
+    // 注册顺序与优先级相反，验证按段比较而非按注册顺序
+    let router = Router::new()
+        .get("/videos/*rest", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::text("wildcard"))
+        })
+        .get("/videos/:id", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::text("param"))
+        })
+        .get("/videos/latest", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::text("static"))
+        });
+
+    assert_eq!(text(get(&router, "/videos/latest").unwrap()), "static");
+    assert_eq!(text(get(&router, "/videos/42").unwrap()), "param");
+    assert_eq!(text(get(&router, "/videos/42/hls").unwrap()), "wildcard");
+}
+
+#[test]
+fn wrong_method_returns_405_with_allow() {
+    let _host = MockHost::new();
+    let router = Router::new()
+        .get("/videos/:id", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(200))
+        })
+        .put("/videos/:id", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(200))
+        })
+        .post("/uploads", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(201))
+        });
+
+    let resp = router.dispatch(request("delete", "/videos/1")).unwrap();
+    assert_eq!(resp.status, 405);
+    assert_eq!(resp.header("Allow"), Some("GET, PUT, HEAD"));
+    let problem: serde_json::Value = serde_json::from_str(&text(resp)).unwrap();
+    assert_eq!(problem["code"], "method_not_allowed");
+    assert_eq!(problem["allow"], serde_json::json!(["GET", "PUT", "HEAD"]));
+
+    let resp = router.dispatch(request("GET", "/uploads")).unwrap();
+    assert_eq!(resp.status, 405);
+    assert_eq!(resp.header("Allow"), Some("POST"));
+
+    // 方法名大小写不敏感
+    assert_eq!(
+        router.dispatch(request("put", "/videos/1")).unwrap().status,
+        200
+    );
+}
+
+#[test]
+fn head_falls_back_to_get_without_body() {
+    let _host = MockHost::new();
+    let router = Router::new()
+        .get("/videos/:id", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::new(200)
+                .header("ETag", "\"v1\"")
+                .body("payload")
+                .build())
+        })
+        .route("HEAD", "/explicit", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(204))
+        })
+        .get("/explicit", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(200))
+        });
+
+    let resp = router.dispatch(request("HEAD", "/videos/1")).unwrap();
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.header("ETag"), Some("\"v1\""));
+    assert!(resp.body.is_none());
+
+    assert_eq!(
+        router
+            .dispatch(request("HEAD", "/explicit"))
+            .unwrap()
+            .status,
+        204
+    );
+    assert_eq!(
+        router.dispatch(request("HEAD", "/missing")).unwrap().status,
+        404
+    );
+}
+
+#[test]
+fn fallback_handles_unmatched_paths() {
+    let _host = MockHost::new();
+    let router = Router::new().get("/videos", |_: &Request, _: &Params| {
+        Ok(ResponseBuilder::status(200))
+    });
+    assert_eq!(get(&router, "/nope").unwrap().status, 404);
+
+    let router = Router::new()
+        .get("/videos", |_: &Request, _: &Params| {
+            Ok(ResponseBuilder::status(200))
+        })
+        .fallback(|req: &Request, params: &Params| {
+            assert!(params.is_empty());
+            Ok(ResponseBuilder::text(format!("fallback {}", req.path)))
+        });
+    assert_eq!(text(get(&router, "/nope").unwrap()), "fallback /nope");
+    // 路径命中但方法不匹配时返回 405，不进入 fallback
+    assert_eq!(
+        router.dispatch(request("POST", "/videos")).unwrap().status,
+        405
+    );
+}
+
+#[test]
+fn extractor_failures_map_to_status() {
+    let host = MockHost::new();
+    let router = Router::new()
+        .get("/videos/:id", |Path(id): Path<i64>| {
+            Ok(ResponseBuilder::text(id.to_string()))
+        })
+        .get("/list", |Query(paging): Query<Paging>| {
+            Ok(ResponseBuilder::text(paging.page.to_string()))
+        })
+        .get("/me", |user: CurrentUser| {
+            Ok(ResponseBuilder::text(user.user_id.clone()))
+        })
+        .post("/videos", |Json(video): Json<NewVideo>| {
+            Ok(ResponseBuilder::text(video.title))
+        });
+
+    assert_eq!(get(&router, "/videos/abc").err().unwrap().status(), 400);
+    assert_eq!(get(&router, "/list?page=-1").err().unwrap().status(), 400);
+    assert_eq!(get(&router, "/list").err().unwrap().status(), 400);
+    assert_eq!(text(get(&router, "/list?page=2").unwrap()), "2");
+
+    assert_eq!(get(&router, "/me").err().unwrap().status(), 401);
+    host.login("u1", "alice", &[]);
+    assert_eq!(text(get(&router, "/me").unwrap()), "u1");
+
+    let mut req = request("POST", "/videos");
+    req.body = Some(stream::memory_buffer(r#"{"title": 1}"#));
+    assert_eq!(router.dispatch(req).err().unwrap().status(), 400);
+
+    let mut req = request("POST", "/videos");
+    req.body = Some(stream::memory_buffer(r#"{"title": "intro"}"#));
+    assert_eq!(text(router.dispatch(req).unwrap()), "intro");
+}
+
+#[test]
+fn optional_extractors_only_absorb_absent_input() {
+    let host = MockHost::new();
+    let router = Router::new()
+        .get("/list", |paging: Option<Query<Paging>>| {
+            Ok(ResponseBuilder::text(format!(
+                "{:?}",
+                paging.map(|q| q.page)
+            )))
+        })
+        .get("/me", |user: Option<CurrentUser>| {
+            Ok(ResponseBuilder::text(format!(
+                "{:?}",
+                user.map(|u| u.user_id.clone())
+            )))
+        })
+        .get("/key", |key: Option<Header<ApiKey>>| {
+            Ok(ResponseBuilder::text(format!("{:?}", key.map(|h| h.0.key))))
+        })
+        .post("/videos", |video: Option<Json<NewVideo>>| {
+            Ok(ResponseBuilder::text(format!(
+                "{:?}",
+                video.map(|v| v.0.title)
+            )))
+        });
+
+    assert_eq!(text(get(&router, "/list").unwrap()), "None");
+    assert_eq!(text(get(&router, "/list?page=3").unwrap()), "Some(3)");
+    assert_eq!(get(&router, "/list?page=abc").err().unwrap().status(), 400);
+
+    assert_eq!(text(get(&router, "/me").unwrap()), "None");
+    host.login("u1", "alice", &[]);
+    assert_eq!(text(get(&router, "/me").unwrap()), "Some(\"u1\")");
+
+    assert_eq!(text(get(&router, "/key").unwrap()), "None");
+    let mut req = request("GET", "/key");
+    req.headers = vec![("X-Api-Key".into(), "secret".into())];
+    assert_eq!(text(router.dispatch(req).unwrap()), "Some(\"secret\")");
+
+    assert_eq!(
+        text(router.dispatch(request("POST", "/videos")).unwrap()),
+        "None"
+    );
+    let mut req = request("POST", "/videos");
+    req.body = Some(stream::memory_buffer("{not json"));
+    assert_eq!(router.dispatch(req).err().unwrap().status(), 400);
+}
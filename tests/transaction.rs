@@ -0,0 +1,107 @@
+#![cfg(feature = "testing")]
+
+use std::panic::{self, AssertUnwindSafe};
+use vtx_sdk::prelude::*;
+use vtx_sdk::testing::MockHost;
+
+fn setup() -> MockHost {
+    let host = MockHost::new();
+    host.execute_sql("CREATE TABLE videos (id INTEGER PRIMARY KEY, title TEXT NOT NULL)")
+        .unwrap();
+    host
+}
+
+fn titles() -> Vec<String> {
+    db::query::<serde_json::Value>("SELECT title FROM videos ORDER BY id", &[])
+        .unwrap()
+        .into_iter()
+        .map(|row| row["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn commits_on_ok() {
+    let _host = setup();
+
+    let depth = db::transaction(|tx| {
+        tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"a"])?;
+        tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"b"])?;
+        Ok(tx.depth())
+    })
+    .unwrap();
+
+    assert_eq!(depth, 1);
+    assert_eq!(titles(), ["a", "b"]);
+    assert!(!db::in_transaction());
+}
+
+#[test]
+fn rolls_back_on_err() {
+    let _host = setup();
+
+    let err = db::transaction(|tx| {
+        tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"a"])?;
+        Err::<(), _>(VtxError::Conflict("stop".into()))
+    })
+    .unwrap_err();
+
+    assert!(matches!(err, VtxError::Conflict(_)));
+    assert!(titles().is_empty());
+    assert!(!db::in_transaction());
+}
+
+#[test]
+fn rolls_back_on_panic() {
+    let _host = setup();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        db::transaction(|tx| -> VtxResult<()> {
+            tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"a"])?;
+            panic!("handler bug");
+        })
+    }));
+
+    assert!(result.is_err());
+    assert!(titles().is_empty());
+    assert!(!db::in_transaction());
+
+    // 守卫回滚后连接可继续开启新事务
+    db::transaction(|tx| tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"b"])).unwrap();
+    assert_eq!(titles(), ["b"]);
+}
+
+#[test]
+fn nested_savepoint_rolls_back_independently() {
+    let _host = setup();
+
+    db::transaction(|tx| {
+        tx.execute("INSERT INTO videos (title) VALUES (?)", &[&"outer"])?;
+
+        let inner = tx.transaction(|inner| {
+            assert_eq!(inner.depth(), 2);
+            inner.execute("INSERT INTO videos (title) VALUES (?)", &[&"discarded"])?;
+            Err::<(), _>(VtxError::BadRequest("inner".into()))
+        });
+        assert!(inner.is_err());
+
+        tx.transaction(|inner| inner.execute("INSERT INTO videos (title) VALUES (?)", &[&"kept"]))?;
+        assert_eq!(titles(), ["outer", "kept"]);
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(titles(), ["outer", "kept"]);
+}
+
+#[test]
+fn outer_rollback_discards_released_savepoints() {
+    let _host = setup();
+
+    let result = db::transaction(|tx| {
+        tx.transaction(|inner| inner.execute("INSERT INTO videos (title) VALUES (?)", &[&"a"]))?;
+        Err::<(), _>(VtxError::Internal("abort".into()))
+    });
+
+    assert!(result.is_err());
+    assert!(titles().is_empty());
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "testing")]
+
+use vtx_sdk::db::{self, DbValue, Json, TryToDbValue};
+use vtx_sdk::testing::MockHost;
+
+/// 按引用传参（如构造器的 `where_eq("id", &id)`）
+fn encode(value: impl TryToDbValue) -> vtx_sdk::error::VtxResult<DbValue> {
+    value.try_to_db_value()
+}
+
+#[test]
+fn wide_integers_fail_instead_of_wrapping() {
+    assert!(matches!(
+        (i64::MAX as u64).try_to_db_value(),
+        Ok(DbValue::Integer(i64::MAX))
+    ));
+    let err = (i64::MAX as u64 + 1).try_to_db_value().unwrap_err();
+    assert_eq!(err.status(), 500);
+
+    let id: &usize = &7;
+    assert!(matches!(encode(id), Ok(DbValue::Integer(7))));
+    assert!(matches!(
+        Some(u64::MAX).try_to_db_value(),
+        Err(vtx_sdk::error::VtxError::Internal(_))
+    ));
+    assert!(matches!(
+        None::<u64>.try_to_db_value(),
+        Ok(DbValue::NullVal)
+    ));
+}
+
+#[test]
+fn execute_rejects_unrepresentable_params() {
+    let _host = MockHost::new();
+    db::execute("CREATE TABLE t (n INTEGER)", &[]).unwrap();
+
+    assert!(db::execute("INSERT INTO t (n) VALUES (?)", &[&u64::MAX]).is_err());
+    assert_eq!(
+        db::execute("INSERT INTO t (n) VALUES (?)", &[&42u64]).unwrap(),
+        1
+    );
+    let n: u64 = db::query_scalar("SELECT n FROM t", &[]).unwrap();
+    assert_eq!(n, 42);
+}
+
+#[test]
+fn json_wrapper_reports_serialization_failure() {
+    use std::collections::BTreeMap;
+
+    let ok = &Json(vec!["rust", "wasm"]);
+    assert!(matches!(
+        encode(ok),
+        Ok(DbValue::Text(ref s)) if s == r#"["rust","wasm"]"#
+    ));
+
+    // 非字符串键的 map 无法序列化为 JSON
+    let bad = Json(BTreeMap::from([((1, 2), 3)]));
+    assert_eq!(bad.try_to_db_value().unwrap_err().status(), 500);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_dates_outside_fixed_width_years_fail() {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    let date = NaiveDate::from_ymd_opt(9999, 12, 31).unwrap();
+    assert!(matches!(
+        date.try_to_db_value(),
+        Ok(DbValue::Text(ref s)) if s == "9999-12-31"
+    ));
+
+    let far = NaiveDate::from_ymd_opt(10000, 1, 1).unwrap();
+    assert!(far.try_to_db_value().is_err());
+    assert!(far.and_hms_opt(0, 0, 0).unwrap().try_to_db_value().is_err());
+    assert!(Utc
+        .with_ymd_and_hms(-1, 1, 1, 0, 0, 0)
+        .unwrap()
+        .try_to_db_value()
+        .is_err());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_dates_outside_fixed_width_years_fail() {
+    use time::{Date, Month};
+
+    let date = &Date::from_calendar_date(2024, Month::May, 1).unwrap();
+    assert!(matches!(
+        encode(date),
+        Ok(DbValue::Text(ref s)) if s == "2024-05-01"
+    ));
+
+    let early = Date::from_calendar_date(-1, Month::January, 1).unwrap();
+    assert!(early.try_to_db_value().is_err());
+    assert!(early.midnight().try_to_db_value().is_err());
+    assert!(early.midnight().assume_utc().try_to_db_value().is_err());
+}
@@ -0,0 +1,359 @@
+//! Derive macros for `vtx-sdk`.
+//!
+//! 请通过 `vtx-sdk` 的 `derive` 特性使用（`vtx_sdk::db::{DbRow, DbTable}`、
+//! `vtx_sdk::migrations::include_migrations!`），生成的代码依赖 `vtx_sdk` 中的运行时类型。
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr};
+
+/// 从查询结果行构造结构体（实现 `vtx_sdk::db::DbRow`）
+///
+/// 字段属性：
+/// - `#[db(rename = "column")]`：映射到不同的列名
+/// - `#[db(default)]`：列缺失或为 NULL 时使用 `Default::default()`
+/// - `#[db(json)]`：列内容为 JSON 文本，反序列化为字段类型
+/// - `#[db(skip)]`：不从结果行读取，始终使用 `Default::default()`
+/// - `#[db(primary_key)]`：主键（供 `DbTable` 使用）
+///
+/// `bool` 字段同时接受 `0/1` 整数与 JSON 布尔值。
+#[proc_macro_derive(DbRow, attributes(db))]
+pub fn derive_db_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_db_row(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// 生成单表 CRUD（实现 `vtx_sdk::db::DbTable`，需同时派生 `DbRow`）
+///
+/// 结构体属性：
+/// - `#[db(table = "videos")]`：表名（默认为结构体名的 snake_case）
+///
+/// 主键为标注 `#[db(primary_key)]` 的字段，未标注时使用名为 `id` 的字段。
+#[proc_macro_derive(DbTable, attributes(db))]
+pub fn derive_db_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_db_table(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// =====================
+// 属性解析
+// =====================
+
+struct FieldSpec {
+    ident: Ident,
+    column: String,
+    default: bool,
+    json: bool,
+    skip: bool,
+    primary_key: bool,
+}
+
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<FieldSpec>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "DbRow / DbTable can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "DbRow / DbTable require a struct with named fields",
+        ));
+    };
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            let mut spec = FieldSpec {
+                column: ident.to_string().trim_start_matches("r#").to_string(),
+                ident,
+                default: false,
+                json: false,
+                skip: false,
+                primary_key: false,
+            };
+
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("db")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        spec.column = meta.value()?.parse::<LitStr>()?.value();
+                    } else if meta.path.is_ident("default") {
+                        spec.default = true;
+                    } else if meta.path.is_ident("json") {
+                        spec.json = true;
+                    } else if meta.path.is_ident("skip") {
+                        spec.skip = true;
+                    } else if meta.path.is_ident("primary_key") {
+                        spec.primary_key = true;
+                    } else {
+                        return Err(meta.error(
+                            "unknown db attribute; expected rename, default, json, skip or primary_key",
+                        ));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            Ok(spec)
+        })
+        .collect()
+}
+
+fn parse_table_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut table = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("db")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown db attribute; expected table"))
+            }
+        })?;
+    }
+    Ok(table.unwrap_or_else(|| snake_case(&input.ident.to_string())))
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// =====================
+// 代码生成
+// =====================
+
+fn expand_db_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let inits = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let column = &f.column;
+        let getter = match (f.skip, f.json, f.default) {
+            (true, _, _) => return quote!(#ident: ::core::default::Default::default()),
+            (false, false, false) => quote!(get),
+            (false, false, true) => quote!(get_or_default),
+            (false, true, false) => quote!(get_json),
+            (false, true, true) => quote!(get_json_or_default),
+        };
+        quote!(#ident: row.#getter(#column)?)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::vtx_sdk::db::DbRow for #name #ty_generics #where_clause {
+            fn from_row(row: &::vtx_sdk::db::Row) -> ::vtx_sdk::error::VtxResult<Self> {
+                ::core::result::Result::Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
+
+fn expand_db_table(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = parse_fields(input)?;
+    let table = parse_table_name(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let columns: Vec<&FieldSpec> = fields.iter().filter(|f| !f.skip).collect();
+
+    let mut keys = columns.iter().filter(|f| f.primary_key);
+    let pk = match (keys.next(), keys.next()) {
+        (Some(pk), None) => *pk,
+        (Some(_), Some(extra)) => {
+            return Err(syn::Error::new_spanned(
+                &extra.ident,
+                "only one field may be marked #[db(primary_key)]",
+            ))
+        }
+        (None, _) => *columns.iter().find(|f| f.ident == "id").ok_or_else(|| {
+            syn::Error::new_spanned(
+                name,
+                "DbTable requires a field named `id` or marked #[db(primary_key)]",
+            )
+        })?,
+    };
+
+    let column_names = columns.iter().map(|f| &f.column);
+    let values = columns.iter().map(|f| field_value(f));
+    let pk_column = &pk.column;
+    let pk_value = field_value(pk);
+
+    Ok(quote! {
+        impl #impl_generics ::vtx_sdk::db::DbTable for #name #ty_generics #where_clause {
+            const TABLE: &'static str = #table;
+            const PRIMARY_KEY: &'static str = #pk_column;
+
+            fn columns() -> &'static [&'static str] {
+                &[#(#column_names),*]
+            }
+
+            fn values(&self) -> ::vtx_sdk::error::VtxResult<::std::vec::Vec<::vtx_sdk::db::DbValue>> {
+                ::core::result::Result::Ok(::std::vec![#(#values),*])
+            }
+
+            fn primary_key_value(&self) -> ::vtx_sdk::error::VtxResult<::vtx_sdk::db::DbValue> {
+                ::core::result::Result::Ok(#pk_value)
+            }
+        }
+    })
+}
+
+/// 字段 → `DbValue` 表达式（JSON 列序列化为文本）
+fn field_value(field: &FieldSpec) -> TokenStream2 {
+    let ident = field.ident.to_token_stream();
+    if field.json {
+        quote!(::vtx_sdk::db::json_column(&self.#ident)?)
+    } else {
+        quote!(::vtx_sdk::db::TryToDbValue::try_to_db_value(&self.#ident)?)
+    }
+}
+
+// =====================
+// include_migrations!
+// =====================
+
+/// 编译期嵌入迁移目录（见 `vtx_sdk::migrations::include_migrations`）
+///
+/// 每个文件经 `include_str!` 嵌入，修改内容会触发重新编译；目录中新增或删除文件则需要
+/// 调用方在 `build.rs` 中输出 `cargo:rerun-if-changed=<dir>`。
+#[proc_macro]
+pub fn include_migrations(input: TokenStream) -> TokenStream {
+    let dir = parse_macro_input!(input as LitStr);
+    expand_include_migrations(&dir)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct MigrationFiles {
+    name: String,
+    up: Option<std::path::PathBuf>,
+    down: Option<std::path::PathBuf>,
+}
+
+fn expand_include_migrations(dir: &LitStr) -> syn::Result<TokenStream2> {
+    let root = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new_spanned(dir, "CARGO_MANIFEST_DIR is not set"))?;
+    let path = std::path::Path::new(&root).join(dir.value());
+
+    let entries = std::fs::read_dir(&path).map_err(|e| {
+        syn::Error::new_spanned(dir, format!("cannot read {}: {}", path.display(), e))
+    })?;
+
+    let mut migrations: std::collections::BTreeMap<u32, MigrationFiles> = Default::default();
+    for entry in entries {
+        let file = entry
+            .map_err(|e| syn::Error::new_spanned(dir, e.to_string()))?
+            .path();
+        let Some(file_name) = file.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(stem) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+
+        let (stem, is_down) = match (stem.strip_suffix(".down"), stem.strip_suffix(".up")) {
+            (Some(stem), _) => (stem, true),
+            (None, Some(stem)) => (stem, false),
+            (None, None) => (stem, false),
+        };
+        let (version, name) = parse_migration_stem(stem).ok_or_else(|| {
+            syn::Error::new_spanned(
+                dir,
+                format!(
+                    "invalid migration file name `{}`; expected `<version>_<name>.sql`",
+                    file_name
+                ),
+            )
+        })?;
+
+        let slot = migrations.entry(version).or_default();
+        if !slot.name.is_empty() && slot.name != name {
+            return Err(syn::Error::new_spanned(
+                dir,
+                format!(
+                    "duplicate migration version {}: `{}` and `{}`",
+                    version, slot.name, name
+                ),
+            ));
+        }
+        slot.name = name;
+
+        let target = if is_down {
+            &mut slot.down
+        } else {
+            &mut slot.up
+        };
+        if target.replace(file.clone()).is_some() {
+            return Err(syn::Error::new_spanned(
+                dir,
+                format!("duplicate migration script for version {}", version),
+            ));
+        }
+    }
+
+    let items = migrations
+        .into_iter()
+        .map(|(version, files)| {
+            let name = &files.name;
+            let up = files.up.ok_or_else(|| {
+                syn::Error::new_spanned(dir, format!("migration {} has no up script", version))
+            })?;
+            let up = up.to_string_lossy().into_owned();
+            let down = match files.down {
+                Some(down) => {
+                    let down = down.to_string_lossy().into_owned();
+                    quote!(::core::option::Option::Some(::core::include_str!(#down)))
+                }
+                None => quote!(::core::option::Option::None),
+            };
+
+            Ok(quote! {
+                ::vtx_sdk::migrations::Migration {
+                    version: #version,
+                    name: #name,
+                    up: ::core::include_str!(#up),
+                    down: #down,
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote!(&[#(#items),*]))
+}
+
+/// `V0001__create_videos` / `0001_create_videos` → `(1, "create_videos")`
+fn parse_migration_stem(stem: &str) -> Option<(u32, String)> {
+    let stem = stem.strip_prefix(['V', 'v']).unwrap_or(stem);
+    let digits = stem
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(stem.len());
+    let version = stem[..digits].parse().ok()?;
+    let name = stem[digits..].trim_start_matches('_');
+    if name.is_empty() {
+        return None;
+    }
+    Some((version, name.to_string()))
+}